@@ -0,0 +1,95 @@
+use advent2024::scan::{self, Grid};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 200;
+
+fn time<T, F: Fn() -> T>(f: F) -> Duration {
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    started.elapsed() / ITERATIONS
+}
+
+fn report<A, B, F: Fn() -> A, G: Fn() -> B>(name: &str, before: F, after: G) {
+    let before = time(before);
+    let after = time(after);
+    println!("{:<6} before {:>10.2?}  after {:>10.2?}  ({:.1}x)", name, before, after, before.as_secs_f64() / after.as_secs_f64());
+}
+
+fn main() {
+    let day01 = include_str!("../res/day01.txt");
+    report("day01", || {
+        day01.lines().map(|line| {
+            let mut split = line.split_whitespace();
+            (split.next().unwrap().parse::<u64>().unwrap(), split.next().unwrap().parse::<u64>().unwrap())
+        }).collect::<Vec<_>>()
+    }, || {
+        day01.lines().map(|line| scan::fixed::<u64, 2>(line, " ").unwrap()).collect::<Vec<_>>()
+    });
+
+    let day02 = include_str!("../res/day02.txt");
+    report("day02", || {
+        day02.lines().map(|line| line.split_ascii_whitespace().map(|value| value.parse::<i64>().unwrap()).collect::<Vec<_>>()).collect::<Vec<_>>()
+    }, || {
+        day02.lines().map(|line| scan::integers::<i64>(line, " ").map(Result::unwrap).collect::<Vec<_>>()).collect::<Vec<_>>()
+    });
+
+    let day07 = include_str!("../res/day07.txt");
+    report("day07", || {
+        day07.lines().map(|line| {
+            let (total, operands) = line.split_once(": ").unwrap();
+            (total.parse::<u64>().unwrap(), operands.split_whitespace().map(|operand| operand.parse::<u64>().unwrap()).collect::<Vec<_>>())
+        }).collect::<Vec<_>>()
+    }, || {
+        day07.lines().map(|line| {
+            let mut numbers = scan::integers::<u64>(line, ": ").map(Result::unwrap);
+            (numbers.next().unwrap(), numbers.collect::<Vec<_>>())
+        }).collect::<Vec<_>>()
+    });
+
+    let day10 = include_str!("../res/day10.txt");
+    report("day10", || {
+        day10.lines().map(|line| line.bytes().map(|byte| byte - b'0').collect::<Vec<_>>()).collect::<Vec<_>>()
+    }, || {
+        Grid::parse(day10).unwrap()
+    });
+
+    let day13 = include_str!("../res/day13.txt");
+    report("day13", || {
+        day13.split("\n\n").map(|machine| {
+            let mut lines = machine.splitn(3, "\n");
+            let button_a = lines.next().unwrap();
+            let button_b = lines.next().unwrap();
+            let prize = lines.next().unwrap();
+            [
+                button_a["Button A: X+".len()..button_a.find(",").unwrap()].parse::<i128>().unwrap(),
+                button_a[button_a.rfind("+").unwrap() + 1..].parse::<i128>().unwrap(),
+                button_b["Button B: X+".len()..button_b.find(",").unwrap()].parse::<i128>().unwrap(),
+                button_b[button_b.rfind("+").unwrap() + 1..].parse::<i128>().unwrap(),
+                prize["Prize: X=".len()..prize.find(",").unwrap()].parse::<i128>().unwrap(),
+                prize[prize.rfind("=").unwrap() + 1..].parse::<i128>().unwrap(),
+            ]
+        }).collect::<Vec<_>>()
+    }, || {
+        day13.split("\n\n").map(|machine| {
+            let mut lines = machine.lines();
+            let [a_x, a_y] = scan::record::<i128, 2>(lines.next().unwrap(), "Button A", "XY+, ").unwrap();
+            let [b_x, b_y] = scan::record::<i128, 2>(lines.next().unwrap(), "Button B", "XY+, ").unwrap();
+            let [p_x, p_y] = scan::record::<i128, 2>(lines.next().unwrap(), "Prize", "XY=, ").unwrap();
+            [a_x, a_y, b_x, b_y, p_x, p_y]
+        }).collect::<Vec<_>>()
+    });
+
+    let day14 = include_str!("../res/day14.txt");
+    report("day14", || {
+        day14.lines().map(|line| {
+            let (pos_x, pos_y) = line[2..line.find(" ").unwrap()].split_once(",").unwrap();
+            let (vel_x, vel_y) = line[(line.find("v=").unwrap() + 2)..].split_once(",").unwrap();
+            [pos_x.parse::<i64>().unwrap(), pos_y.parse::<i64>().unwrap(), vel_x.parse::<i64>().unwrap(), vel_y.parse::<i64>().unwrap()]
+        }).collect::<Vec<_>>()
+    }, || {
+        day14.lines().map(|line| scan::fixed::<i64, 4>(line, "pv=, ").unwrap()).collect::<Vec<_>>()
+    });
+}
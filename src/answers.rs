@@ -0,0 +1,64 @@
+use crate::config;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+pub const DEFAULT_PATH: &str = "res/answers.toml";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    pub answers: Vec<(u8, u8, String)>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers> {
+        let mut answers = vec![];
+        for section in config::parse(text)?.into_iter().filter(|section| !section.name.is_empty()) {
+            let day = section.name.strip_prefix("day").and_then(|day| day.parse().ok())
+                .with_context(|| format!("Unexpected answers section: {}", section.name))?;
+            for (key, value) in &section.entries {
+                let part = key.strip_prefix("part").and_then(|part| part.parse().ok())
+                    .with_context(|| format!("Unexpected key {} for day {}", key, day))?;
+                answers.push((day, part, value.clone()));
+            }
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Answers> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+        let text = fs::read_to_string(path).with_context(|| format!("Couldn't read {}", path.display()))?;
+        Answers::parse(&text).with_context(|| format!("Couldn't parse {}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.iter().find(|(d, p, _)| *d == day && *p == part).map(|(_, _, answer)| answer.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_answers() -> Result<()> {
+        let answers = Answers::parse("[day01]\npart1 = 11\npart2 = 31\n\n[day11]\npart1 = 55312\n")?;
+        assert_eq!(answers.get(1, 2), Some("31"));
+        assert_eq!(answers.get(11, 1), Some("55312"));
+        assert_eq!(answers.get(11, 2), None);
+        assert_eq!(Answers::parse("[day01]\nanswer = 1\n").unwrap_err().to_string(), "Unexpected key answer for day 1");
+        Ok(())
+    }
+
+    #[test]
+    fn answers_file_covers_every_solver() -> Result<()> {
+        let answers = Answers::load(DEFAULT_PATH)?;
+        for solver in crate::solvers::SOLVERS {
+            assert!(answers.get(solver.day, solver.part).is_some(), "No known answer for day {} part {}", solver.day, solver.part);
+        }
+        Ok(())
+    }
+}
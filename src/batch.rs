@@ -0,0 +1,169 @@
+use crate::cancel::{Cancel, Cancelled};
+use crate::config;
+use crate::params::Params;
+use crate::progress::NoProgress;
+use crate::solvers;
+use anyhow::{bail, Context, Result};
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const EXPECTED_EXTENSION: &str = "expected";
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Answered(String),
+    Matched(String),
+    Mismatched { answer: String, expected: String },
+    Failed(String),
+    TimedOut,
+    Panicked(String),
+}
+
+impl Status {
+    pub fn is_flagged(&self) -> bool {
+        matches!(self, Status::Mismatched { .. } | Status::Failed(_) | Status::TimedOut | Status::Panicked(_))
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Answered(answer) => write!(f, "{}", answer),
+            Status::Matched(answer) => write!(f, "{} (matches expected)", answer),
+            Status::Mismatched { answer, expected } => write!(f, "{} (MISMATCH, expected {})", answer, expected),
+            Status::Failed(error) => write!(f, "ERROR {}", error),
+            Status::TimedOut => f.write_str("TIMEOUT"),
+            Status::Panicked(message) => write!(f, "PANIC {}", message),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub parts: Vec<(u8, Status)>,
+}
+
+impl FileReport {
+    pub fn is_flagged(&self) -> bool {
+        self.parts.iter().any(|(_, status)| status.is_flagged())
+    }
+}
+
+impl Display for FileReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}:", self.path.display())?;
+        for (part, status) in &self.parts {
+            writeln!(f, "  Part {}: {}", part, status)?;
+        }
+        Ok(())
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().map(|message| message.to_string()).unwrap_or_else(|| "unknown panic".to_string()),
+    }
+}
+
+fn expected_answers(path: &Path) -> Result<Vec<(String, String)>> {
+    let mut sidecar = path.as_os_str().to_owned();
+    sidecar.push(format!(".{}", EXPECTED_EXTENSION));
+    let sidecar = PathBuf::from(sidecar);
+    if !sidecar.exists() {
+        return Ok(vec![]);
+    }
+    let text = fs::read_to_string(&sidecar).with_context(|| format!("Couldn't read {}", sidecar.display()))?;
+    let sections = config::parse(&text).with_context(|| format!("Couldn't parse {}", sidecar.display()))?;
+    Ok(sections.into_iter().next().map(|section| section.entries).unwrap_or_default())
+}
+
+pub fn run_file(day: u8, path: &Path, params: &Params, timeout: Option<Duration>) -> Result<FileReport> {
+    if !solvers::SOLVERS.iter().any(|solver| solver.day == day) {
+        bail!("No solvers for day {}", day);
+    }
+    let data = fs::read_to_string(path).with_context(|| format!("Couldn't read {}", path.display()))?;
+    let data = data.trim_end();
+    let expected = expected_answers(path)?;
+    let mut parts = vec![];
+    for solver in solvers::SOLVERS.iter().filter(|solver| solver.day == day) {
+        let cancel = timeout.map(Cancel::with_timeout).unwrap_or_default();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| (solver.solve)(data, params, &cancel, &NoProgress)));
+        let expected = expected.iter().find(|(key, _)| *key == format!("part{}", solver.part)).map(|(_, value)| value);
+        let status = match (result, expected) {
+            (Err(payload), _) => Status::Panicked(panic_message(payload)),
+            (Ok(Err(e)), _) if e.downcast_ref::<Cancelled>() == Some(&Cancelled::TimedOut) => Status::TimedOut,
+            (Ok(Err(e)), _) => Status::Failed(format!("{:#}", e)),
+            (Ok(Ok(answer)), None) => Status::Answered(answer),
+            (Ok(Ok(answer)), Some(expected)) if answer == *expected => Status::Matched(answer),
+            (Ok(Ok(answer)), Some(expected)) => Status::Mismatched { answer, expected: expected.clone() },
+        };
+        parts.push((solver.part, status));
+    }
+    Ok(FileReport { path: path.to_path_buf(), parts })
+}
+
+pub fn run_dir(day: u8, dir: &Path, params: &Params, timeout: Option<Duration>) -> Result<Vec<FileReport>> {
+    let mut paths = vec![];
+    for entry in fs::read_dir(dir).with_context(|| format!("Couldn't read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_none_or(|extension| extension != EXPECTED_EXTENSION) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    paths.iter().map(|path| run_file(day, path, params, timeout)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_answers_mismatches_and_failures() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("advent2024-batch-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("alice.txt"), "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n")?;
+        fs::write(dir.join("alice.txt.expected"), "part1 = 11\npart2 = 30\n")?;
+        fs::write(dir.join("bob.txt"), "3   4\n4   3\n")?;
+        fs::write(dir.join("bob.txt.expected"), "part1 = 3\n")?;
+        fs::write(dir.join("carol.txt"), "not numbers")?;
+        let reports = run_dir(1, &dir, &Params::default(), None);
+        fs::remove_dir_all(&dir)?;
+        let reports = reports?;
+        assert_eq!(reports.len(), 3);
+        assert_eq!(reports[0].parts, vec![(1, Status::Matched("11".to_string())), (2, Status::Mismatched { answer: "31".to_string(), expected: "30".to_string() })]);
+        assert_eq!(reports[1].parts, vec![(1, Status::Mismatched { answer: "0".to_string(), expected: "3".to_string() }), (2, Status::Answered("7".to_string()))]);
+        assert!(matches!(reports[2].parts[0].1, Status::Failed(_)));
+        assert!(reports.iter().all(FileReport::is_flagged));
+        Ok(())
+    }
+
+    #[test]
+    fn reports_timeouts() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("advent2024-batch-timeout-{}.txt", std::process::id()));
+        fs::write(&path, "p=0,4 v=3,-3\n")?;
+        let report = run_file(14, &path, &Params::default(), Some(Duration::ZERO));
+        fs::remove_file(&path)?;
+        let report = report?;
+        assert_eq!(report.parts[1], (2, Status::TimedOut));
+        assert!(report.is_flagged());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_days_without_solvers() {
+        assert_eq!(run_file(26, Path::new("day26.txt"), &Params::default(), None).unwrap_err().to_string(), "No solvers for day 26");
+    }
+
+    #[test]
+    fn describes_panics() {
+        assert_eq!(panic_message(panic::catch_unwind(|| panic!("boom {}", 1)).unwrap_err()), "boom 1");
+        assert_eq!(panic_message(panic::catch_unwind(|| panic!("boom")).unwrap_err()), "boom");
+    }
+}
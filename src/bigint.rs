@@ -0,0 +1,252 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use anyhow::{bail, Error, Result};
+use core::cmp::Ordering;
+use core::fmt::{Display, Formatter};
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Mul};
+use core::str::FromStr;
+
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn normalize(mut self) -> BigUint {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        Some(self.limbs.iter().rev().fold(0, |value, &limb| (value << 32) | limb as u128))
+    }
+
+    fn mul_small(&self, factor: u32) -> BigUint {
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = 0u64;
+        for &limb in &self.limbs {
+            let product = limb as u64 * factor as u64 + carry;
+            limbs.push(product as u32);
+            carry = product >> 32;
+        }
+        limbs.push(carry as u32);
+        BigUint { limbs }.normalize()
+    }
+
+    fn div_rem_small(&self, divisor: u32) -> (BigUint, u32) {
+        let mut limbs = vec![0; self.limbs.len()];
+        let mut remainder = 0u64;
+        for (i, &limb) in self.limbs.iter().enumerate().rev() {
+            let value = (remainder << 32) | limb as u64;
+            limbs[i] = (value / divisor as u64) as u32;
+            remainder = value % divisor as u64;
+        }
+        (BigUint { limbs }.normalize(), remainder as u32)
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut value: u128) -> BigUint {
+        let mut limbs = vec![];
+        while value > 0 {
+            limbs.push(value as u32);
+            value >>= 32;
+        }
+        BigUint { limbs }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> BigUint {
+        BigUint::from(value as u128)
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() { (self, other) } else { (other, self) };
+        let mut limbs = Vec::with_capacity(long.limbs.len() + 1);
+        let mut carry = 0u64;
+        for (i, &limb) in long.limbs.iter().enumerate() {
+            let sum = limb as u64 + *short.limbs.get(i).unwrap_or(&0) as u64 + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+        BigUint { limbs }.normalize()
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: BigUint) -> BigUint {
+        &self + &other
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        *self = &*self + other;
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let product = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        BigUint { limbs }.normalize()
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: BigUint) -> BigUint {
+        &self * &other
+    }
+}
+
+impl Sum for BigUint {
+    fn sum<I: Iterator<Item = BigUint>>(iter: I) -> BigUint {
+        iter.fold(BigUint::zero(), |total, value| total + value)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        self.limbs.len().cmp(&other.limbs.len()).then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.is_zero() {
+            return f.write_str("0");
+        }
+        let mut chunks = vec![];
+        let mut value = self.clone();
+        while !value.is_zero() {
+            let (quotient, remainder) = value.div_rem_small(DECIMAL_CHUNK);
+            chunks.push(remainder);
+            value = quotient;
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:0width$}", chunk, width = DECIMAL_CHUNK_DIGITS)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<BigUint> {
+        if text.is_empty() || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+            bail!("Invalid digit in number: {}", text);
+        }
+        let mut value = BigUint::zero();
+        for chunk in text.as_bytes().chunks(DECIMAL_CHUNK_DIGITS) {
+            let digits: u32 = core::str::from_utf8(chunk)?.parse()?;
+            value = &value.mul_small(10u32.pow(chunk.len() as u32)) + &BigUint::from(digits as u64);
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for BigUint {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BigUint {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<BigUint, D::Error> {
+        let text = <alloc::string::String as serde::Deserialize>::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_and_multiplies_beyond_u128() -> Result<()> {
+        let max = BigUint::from(u128::MAX);
+        assert_eq!((&max + &BigUint::from(1u64)).to_string(), "340282366920938463463374607431768211456");
+        assert_eq!((&max * &max).to_string(), "115792089237316195423570985008687907852589419931798687112530834793049593217025");
+        assert_eq!((&max + &BigUint::from(1u64)).to_u128(), None);
+        assert_eq!(BigUint::from(12345u64).to_u128(), Some(12345));
+        Ok(())
+    }
+
+    #[test]
+    fn parses_and_displays() -> Result<()> {
+        for text in ["0", "7", "1000000000", "123456789012345678901234567890123456789012345"] {
+            assert_eq!(text.parse::<BigUint>()?.to_string(), text);
+        }
+        assert_eq!("0012".parse::<BigUint>()?, BigUint::from(12u64));
+        assert!("12a".parse::<BigUint>().is_err());
+        assert!("".parse::<BigUint>().is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_as_decimal_string() -> Result<()> {
+        let big: BigUint = "123456789012345678901234567890123456789012345".parse()?;
+        let json = serde_json::to_string(&big)?;
+        assert_eq!(json, "\"123456789012345678901234567890123456789012345\"");
+        assert_eq!(serde_json::from_str::<BigUint>(&json)?, big);
+        assert!(serde_json::from_str::<BigUint>("\"12a\"").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn orders_by_magnitude() -> Result<()> {
+        let big: BigUint = "100000000000000000000000000000000000000000".parse()?;
+        assert!(big > BigUint::from(u128::MAX));
+        assert!(BigUint::from(5u64) < BigUint::from(7u64));
+        assert_eq!(vec![BigUint::from(1u64), BigUint::from(2u64)].into_iter().sum::<BigUint>(), BigUint::from(3u64));
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A fixed size grid of booleans packed one bit per cell, for visited and membership maps.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> BitGrid {
+        BitGrid { width, height, words: vec![0; (width * height).div_ceil(WORD_BITS)] }
+    }
+
+    pub fn from_rows(rows: &[Vec<bool>]) -> BitGrid {
+        let mut grid = BitGrid::new(rows.first().map_or(0, Vec::len), rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, &set)| set) {
+                grid.set(x, y);
+            }
+        }
+        grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn locate(&self, x: usize, y: usize) -> (usize, u64) {
+        assert!(x < self.width && y < self.height, "({}, {}) is outside a {}x{} grid", x, y, self.width, self.height);
+        let index = y * self.width + x;
+        (index / WORD_BITS, 1 << (index % WORD_BITS))
+    }
+
+    pub fn test(&self, x: usize, y: usize) -> bool {
+        let (word, bit) = self.locate(x, y);
+        self.words[word] & bit != 0
+    }
+
+    /// Sets the cell, returning whether it was previously clear.
+    pub fn set(&mut self, x: usize, y: usize) -> bool {
+        let (word, bit) = self.locate(x, y);
+        let was_clear = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        was_clear
+    }
+
+    pub fn clear(&mut self, x: usize, y: usize) {
+        let (word, bit) = self.locate(x, y);
+        self.words[word] &= !bit;
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.height).flat_map(move |y| (0..self.width).map(move |x| (x, y))).filter(|&(x, y)| self.test(x, y))
+    }
+
+    fn combine(&self, other: &BitGrid, op: fn(u64, u64) -> u64) -> BitGrid {
+        assert_eq!((self.width, self.height), (other.width, other.height), "Combining grids of different sizes");
+        let words = self.words.iter().zip(&other.words).map(|(&a, &b)| op(a, b)).collect();
+        BitGrid { width: self.width, height: self.height, words }
+    }
+
+    pub fn union(&self, other: &BitGrid) -> BitGrid {
+        self.combine(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &BitGrid) -> BitGrid {
+        self.combine(other, |a, b| a & b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sets_tests_and_clears_cells() {
+        let mut grid = BitGrid::new(10, 9);
+        assert!(grid.set(9, 8));
+        assert!(!grid.set(9, 8));
+        grid.set(0, 0);
+        grid.set(4, 6);
+        assert!(grid.test(4, 6));
+        assert!(!grid.test(6, 4));
+        grid.clear(0, 0);
+        assert_eq!(grid.count(), 2);
+        assert_eq!(grid.iter().collect::<Vec<_>>(), vec![(4, 6), (9, 8)]);
+    }
+
+    #[test]
+    fn combines_grids() {
+        let a = BitGrid::from_rows(&[vec![true, true, false], vec![false, false, true]]);
+        let b = BitGrid::from_rows(&[vec![false, true, true], vec![false, false, true]]);
+        assert_eq!(a.union(&b).count(), 4);
+        assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), vec![(1, 0), (2, 1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is outside a 3x2 grid")]
+    fn rejects_cells_outside_grid() {
+        BitGrid::new(3, 2).test(3, 0);
+    }
+}
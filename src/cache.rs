@@ -0,0 +1,133 @@
+use crate::config;
+use crate::params::Params;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+pub const DEFAULT_PATH: &str = ".advent2024/cache.toml";
+
+pub(crate) const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub version: String,
+    pub answer: String,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Cache {
+    pub entries: Vec<Entry>,
+}
+
+pub(crate) const fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    let mut i = 0;
+    while i < bytes.len() {
+        hash = (hash ^ bytes[i] as u64).wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
+
+pub fn input_hash(day: u8, data: &str, params: &Params) -> String {
+    let prefix = format!("day{:02}.", day);
+    let mut hash = fnv1a(FNV_OFFSET, data.as_bytes());
+    for (key, value) in params.entries().into_iter().filter(|(key, _)| key.starts_with(&prefix)) {
+        hash = fnv1a(hash, format!("\n{}={}", key, value).as_bytes());
+    }
+    format!("{:016x}", hash)
+}
+
+impl Cache {
+    pub fn parse(text: &str) -> Result<Cache> {
+        let mut entries = vec![];
+        for section in config::parse(text)?.into_iter().filter(|section| !section.name.is_empty()) {
+            let (day, part) = section.name.split_once('.')
+                .and_then(|(day, part)| Some((day.strip_prefix("day")?.parse().ok()?, part.strip_prefix("part")?.parse().ok()?)))
+                .with_context(|| format!("Unexpected cache section: {}", section.name))?;
+            let field = |key: &str| section.get(key).map(str::to_string).with_context(|| format!("Cache entry {} has no {}", section.name, key));
+            entries.push(Entry { day, part, input: field("input")?, version: field("version")?, answer: field("answer")? });
+        }
+        Ok(Cache { entries })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Cache> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Cache::default());
+        }
+        let text = fs::read_to_string(path).with_context(|| format!("Couldn't read {}", path.display()))?;
+        Cache::parse(&text).with_context(|| format!("Couldn't parse {}", path.display()))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("Couldn't create {}", parent.display()))?;
+        }
+        fs::write(path, self.to_text()).with_context(|| format!("Couldn't write {}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: u8, input: &str, version: &str) -> Option<&str> {
+        self.entries.iter()
+            .find(|entry| entry.day == day && entry.part == part && entry.input == input && entry.version == version)
+            .map(|entry| entry.answer.as_str())
+    }
+
+    /// Stores an answer alongside those for other inputs, dropping any for older solver versions.
+    pub fn insert(&mut self, entry: Entry) {
+        self.entries.retain(|other| (other.day, other.part) != (entry.day, entry.part) || (other.input != entry.input && other.version == entry.version));
+        self.entries.push(entry);
+        self.entries.sort_by(|a, b| (a.day, a.part, &a.input).cmp(&(b.day, b.part, &b.input)));
+    }
+
+    pub fn to_text(&self) -> String {
+        self.entries.iter()
+            .map(|entry| format!("[day{:02}.part{}]\ninput = \"{}\"\nversion = \"{}\"\nanswer = \"{}\"\n", entry.day, entry.part, entry.input, entry.version, entry.answer))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: u8, part: u8, input: &str, answer: &str) -> Entry {
+        Entry { day, part, input: input.to_string(), version: "1".to_string(), answer: answer.to_string() }
+    }
+
+    #[test]
+    fn hashes_input_and_day_params() {
+        let params = Params::default();
+        let hash = input_hash(14, "p=0,4 v=3,-3", &params);
+        assert_eq!(hash.len(), 16);
+        assert_eq!(hash, input_hash(14, "p=0,4 v=3,-3", &params));
+        assert_ne!(hash, input_hash(14, "p=0,4 v=3,-2", &params));
+        assert_ne!(hash, input_hash(14, "p=0,4 v=3,-3", &Params { day14_map_x: 11, ..Params::default() }));
+        assert_eq!(hash, input_hash(14, "p=0,4 v=3,-3", &Params { day11_part2_blinks: 40, ..Params::default() }));
+    }
+
+    #[test]
+    fn invalidates_on_input_or_version_change() -> Result<()> {
+        let mut cache = Cache::default();
+        cache.insert(entry(6, 2, "00ff", "1663"));
+        cache.insert(entry(1, 1, "abcd", "11"));
+        assert_eq!(cache.get(6, 2, "00ff", "1"), Some("1663"));
+        assert_eq!(cache.get(6, 2, "00fe", "1"), None);
+        assert_eq!(cache.get(6, 2, "00ff", "2"), None);
+        cache.insert(entry(6, 2, "00fe", "1700"));
+        assert_eq!(cache.get(6, 2, "00ff", "1"), Some("1663"));
+        assert_eq!(cache.get(6, 2, "00fe", "1"), Some("1700"));
+        assert_eq!(Cache::parse(&cache.to_text())?, cache);
+        assert_eq!(cache.entries[0], entry(1, 1, "abcd", "11"));
+        cache.insert(Entry { version: "2".to_string(), ..entry(6, 2, "00ff", "1664") });
+        assert_eq!(cache.get(6, 2, "00ff", "2"), Some("1664"));
+        assert_eq!(cache.get(6, 2, "00fe", "1"), None);
+        assert_eq!(cache.entries.len(), 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use crate::answers::Answers;
+use crate::history::History;
+use crate::solvers;
+use std::time::Duration;
+
+const DAYS: u8 = 25;
+const COLUMNS: u8 = 5;
+const CELL_WIDTH: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    Missing,
+    Unsolved,
+    Unverified,
+    Verified,
+    Wrong,
+}
+
+impl Status {
+    fn mark(self) -> char {
+        match self {
+            Status::Missing => ' ',
+            Status::Unsolved => '.',
+            Status::Unverified => '+',
+            Status::Verified => '*',
+            Status::Wrong => '!',
+        }
+    }
+}
+
+pub fn status(day: u8, part: u8, answers: &Answers, history: &History) -> Status {
+    if solvers::find(day, part).is_none() {
+        return Status::Missing;
+    }
+    match (history.last(day, part), answers.get(day, part)) {
+        (None, _) => Status::Unsolved,
+        (Some(run), Some(known)) if run.answer == known => Status::Verified,
+        (Some(_), Some(_)) => Status::Wrong,
+        (Some(_), None) => Status::Unverified,
+    }
+}
+
+pub fn badge(elapsed: Duration) -> &'static str {
+    match elapsed.as_micros() {
+        0..1_000 => "<1ms",
+        1_000..10_000 => "<10ms",
+        10_000..100_000 => "<100ms",
+        100_000..1_000_000 => "<1s",
+        _ => "1s+",
+    }
+}
+
+fn cell(day: u8, answers: &Answers, history: &History) -> [String; 2] {
+    let marks: String = (1..=2).map(|part| status(day, part, answers, history).mark()).collect();
+    let slowest = (1..=2).filter_map(|part| history.last(day, part)).map(|run| run.elapsed).max();
+    [format!(" {:>2}  {}", day, marks), format!(" {}", slowest.map(badge).unwrap_or(""))]
+}
+
+pub fn render(answers: &Answers, history: &History) -> String {
+    let border = format!("+{}\n", format!("{}+", "-".repeat(CELL_WIDTH)).repeat(COLUMNS as usize));
+    let mut out = border.clone();
+    for week in 0..DAYS.div_ceil(COLUMNS) {
+        let cells: Vec<[String; 2]> = (1..=COLUMNS).map(|column| week * COLUMNS + column).map(|day| cell(day, answers, history)).collect();
+        for line in 0..2 {
+            out.push('|');
+            for cell in &cells {
+                out.push_str(&format!("{:<width$}|", cell[line], width = CELL_WIDTH));
+            }
+            out.push('\n');
+        }
+        out.push_str(&border);
+    }
+    let verified = (1..=DAYS).flat_map(|day| (1..=2).map(move |part| (day, part)))
+        .filter(|&(day, part)| status(day, part, answers, history) == Status::Verified)
+        .count();
+    out.push_str(&format!("{} of {} stars verified\n", verified, DAYS as usize * 2));
+    out.push_str("* verified  + unverified  ! wrong answer  . not run yet\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::Run;
+
+    fn run(day: u8, part: u8, answer: &str, micros: u64) -> Run {
+        Run { day, part, answer: answer.to_string(), elapsed: Duration::from_micros(micros) }
+    }
+
+    #[test]
+    fn picks_runtime_badges() {
+        assert_eq!(badge(Duration::from_micros(999)), "<1ms");
+        assert_eq!(badge(Duration::from_millis(42)), "<100ms");
+        assert_eq!(badge(Duration::from_secs(3)), "1s+");
+    }
+
+    #[test]
+    fn renders_stars_and_badges() -> Result<(), anyhow::Error> {
+        let answers = Answers::parse("[day01]\npart1 = 11\npart2 = 31\n")?;
+        let mut history = History::default();
+        history.record(run(1, 1, "11", 500));
+        history.record(run(1, 2, "30", 20_000));
+        history.record(run(2, 1, "2", 1_500));
+        let calendar = render(&answers, &history);
+        let lines: Vec<&str> = calendar.lines().collect();
+        assert_eq!(lines[0], "+----------+----------+----------+----------+----------+");
+        assert_eq!(lines[1], "|  1  *!   |  2  +.   |  3  ..   |  4  ..   |  5  ..   |");
+        assert_eq!(lines[2], "| <100ms   | <10ms    |          |          |          |");
+        assert_eq!(lines[10], "| 16       | 17       | 18       | 19       | 20       |");
+        assert_eq!(lines[16], "1 of 50 stars verified");
+        assert_eq!(statuses(&answers, &history), vec![Status::Verified, Status::Wrong, Status::Unverified, Status::Unsolved]);
+        Ok(())
+    }
+
+    fn statuses(answers: &Answers, history: &History) -> Vec<Status> {
+        vec![status(1, 1, answers, history), status(1, 2, answers, history), status(2, 1, answers, history), status(2, 2, answers, history)]
+    }
+}
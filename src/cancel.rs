@@ -0,0 +1,90 @@
+use anyhow::Result;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, Default)]
+pub struct Cancel {
+    flag: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Cancelled {
+    Cancelled,
+    TimedOut,
+}
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cancelled::Cancelled => f.write_str("Solver was cancelled"),
+            Cancelled::TimedOut => f.write_str("Solver timed out"),
+        }
+    }
+}
+
+impl Error for Cancelled {}
+
+impl Cancel {
+    pub fn never() -> Cancel {
+        Cancel::default()
+    }
+
+    pub fn with_deadline(deadline: Instant) -> Cancel {
+        Cancel { deadline: Some(deadline), ..Cancel::default() }
+    }
+
+    pub fn with_timeout(timeout: Duration) -> Cancel {
+        Cancel::with_deadline(Instant::now() + timeout)
+    }
+
+    pub fn cancel(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    pub fn status(&self) -> Option<Cancelled> {
+        if self.flag.load(Ordering::Relaxed) {
+            Some(Cancelled::Cancelled)
+        } else if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            Some(Cancelled::TimedOut)
+        } else {
+            None
+        }
+    }
+
+    pub fn check(&self) -> Result<()> {
+        match self.status() {
+            None => Ok(()),
+            Some(cancelled) => Err(cancelled.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn never_cancels_by_itself() {
+        assert!(Cancel::never().check().is_ok());
+    }
+
+    #[test]
+    fn cancels_every_clone() {
+        let cancel = Cancel::never();
+        let clone = cancel.clone();
+        cancel.cancel();
+        assert_eq!(clone.status(), Some(Cancelled::Cancelled));
+        assert_eq!(clone.check().unwrap_err().downcast_ref::<Cancelled>(), Some(&Cancelled::Cancelled));
+    }
+
+    #[test]
+    fn times_out_after_deadline() {
+        assert_eq!(Cancel::with_deadline(Instant::now()).status(), Some(Cancelled::TimedOut));
+        assert_eq!(Cancel::with_timeout(Duration::from_secs(60)).status(), None);
+        assert_eq!(Cancel::with_deadline(Instant::now()).check().unwrap_err().to_string(), "Solver timed out");
+    }
+}
@@ -0,0 +1,200 @@
+use std::fmt::{Display, Formatter};
+
+pub struct Puzzle {
+    pub day: u8,
+    pub title: &'static str,
+    pub released: &'static str,
+    pub parts: [&'static str; 2],
+    pub functions: [&'static str; 2],
+    pub params: &'static [(&'static str, &'static str)],
+}
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle {
+        day: 1,
+        title: "Historian Hysteria",
+        released: "2024-12-01",
+        parts: ["Total distance between the sorted left and right location lists",
+                "Similarity score: each left location times how often it appears on the right"],
+        functions: ["day01::sum_differences", "day01::similarity_score"],
+        params: &[],
+    },
+    Puzzle {
+        day: 2,
+        title: "Red-Nosed Reports",
+        released: "2024-12-02",
+        parts: ["Count reports whose levels steadily increase or decrease",
+                "Count reports that are safe after removing at most one level"],
+        functions: ["day02::count_pure_safe_reports", "day02::count_dampened_safe_reports"],
+        params: &[("day02.max_step", "Largest difference allowed between adjacent levels")],
+    },
+    Puzzle {
+        day: 3,
+        title: "Mull It Over",
+        released: "2024-12-03",
+        parts: ["Sum the products of every mul(x,y) instruction in corrupted memory",
+                "Sum the products of mul instructions enabled by do() and don't()"],
+        functions: ["day03::add_all_muls", "day03::clean_do_donts"],
+        params: &[],
+    },
+    Puzzle {
+        day: 4,
+        title: "Ceres Search",
+        released: "2024-12-04",
+        parts: ["Count every XMAS in the word search, in any direction",
+                "Count every pair of MAS crossing in an X"],
+        functions: ["day04::find_xmas", "day04::find_x_mas"],
+        params: &[],
+    },
+    Puzzle {
+        day: 5,
+        title: "Print Queue",
+        released: "2024-12-05",
+        parts: ["Sum the middle pages of updates already in the right order",
+                "Sum the middle pages of incorrectly ordered updates once fixed"],
+        functions: ["day05::sum_valid_middle_pages", "day05::sum_corrected_invalid_middle_pages"],
+        params: &[],
+    },
+    Puzzle {
+        day: 6,
+        title: "Guard Gallivant",
+        released: "2024-12-06",
+        parts: ["Count the positions the guard visits before leaving the map",
+                "Count the positions where one new obstruction traps the guard in a loop"],
+        functions: ["day06::count_path", "day06::count_guard_loops"],
+        params: &[],
+    },
+    Puzzle {
+        day: 7,
+        title: "Bridge Repair",
+        released: "2024-12-07",
+        parts: ["Sum the test values that + and * operators can produce",
+                "Sum the test values that +, * and concatenation can produce"],
+        functions: ["day07::total_calibration_result", "day07::total_calibration_with_concat_result"],
+        params: &[],
+    },
+    Puzzle {
+        day: 8,
+        title: "Resonant Collinearity",
+        released: "2024-12-08",
+        parts: ["Count unique antinode locations within the map",
+                "Count unique antinode locations including resonant harmonics"],
+        functions: ["day08::unique_antinode_locations", "day08::unique_antinode_locations_with_harmonics"],
+        params: &[],
+    },
+    Puzzle {
+        day: 9,
+        title: "Disk Fragmenter",
+        released: "2024-12-09",
+        parts: ["Checksum of the disk after moving blocks one at a time",
+                "Checksum of the disk after moving whole files"],
+        functions: ["day09::checksum_compacted_disk_naive", "day09::checksum_compacted_disk_smart"],
+        params: &[],
+    },
+    Puzzle {
+        day: 10,
+        title: "Hoof It",
+        released: "2024-12-10",
+        parts: ["Sum the number of peaks reachable from every trailhead",
+                "Sum the number of distinct hiking trails from every trailhead"],
+        functions: ["day10::sum_of_trailhead_unique_ends", "day10::sum_of_trailhead_paths"],
+        params: &[],
+    },
+    Puzzle {
+        day: 11,
+        title: "Plutonian Pebbles",
+        released: "2024-12-11",
+        parts: ["Count the stones after the first run of blinks",
+                "Count the stones after the second, much longer run of blinks"],
+        functions: ["day11::count_stones", "day11::count_stones"],
+        params: &[("day11.part1_blinks", "Number of blinks for part 1"),
+                  ("day11.part2_blinks", "Number of blinks for part 2")],
+    },
+    Puzzle {
+        day: 12,
+        title: "Garden Groups",
+        released: "2024-12-12",
+        parts: ["Total price of fencing every region by area and perimeter",
+                "Total price of fencing every region by area and number of sides"],
+        functions: ["day12::total_fencing_price", "day12::discounted_fencing_price"],
+        params: &[],
+    },
+    Puzzle {
+        day: 13,
+        title: "Claw Contraption",
+        released: "2024-12-13",
+        parts: ["Fewest tokens spent to win every winnable prize",
+                "Fewest tokens spent once every prize has moved much further away"],
+        functions: ["day13::min_spend_for_most_prizes", "day13::min_spend_for_most_prizes"],
+        params: &[("day13.prize_offset", "Distance added to each prize coordinate in part 2")],
+    },
+    Puzzle {
+        day: 14,
+        title: "Restroom Redoubt",
+        released: "2024-12-14",
+        parts: ["Safety factor of the robots in each quadrant after a number of seconds",
+                "Fewest seconds until the robots arrange themselves into a Christmas tree"],
+        functions: ["day14::safety_factor", "day14::find_picture"],
+        params: &[("day14.map_x", "Width of the bathroom"),
+                  ("day14.map_y", "Height of the bathroom"),
+                  ("day14.seconds", "Seconds to simulate for part 1")],
+    },
+    Puzzle {
+        day: 15,
+        title: "Warehouse Woes",
+        released: "2024-12-15",
+        parts: ["Sum of box GPS coordinates after the robot has moved",
+                "Sum of box GPS coordinates in the double-width warehouse"],
+        functions: ["day15::sum_of_gps_coordinates", "day15::sum_of_gps_coordinates"],
+        params: &[],
+    },
+];
+
+pub fn puzzle(day: u8) -> Option<&'static Puzzle> {
+    PUZZLES.iter().find(|puzzle| puzzle.day == day)
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Day {}: {} (released {})", self.day, self.title, self.released)?;
+        for (i, (part, function)) in self.parts.iter().zip(self.functions).enumerate() {
+            writeln!(f, "  Part {}: {} [{}]", i + 1, part, function)?;
+        }
+        for (name, meaning) in self.params {
+            writeln!(f, "  {}: {}", name, meaning)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::Params;
+    use crate::solvers::SOLVERS;
+
+    #[test]
+    fn every_solver_has_a_puzzle() {
+        for solver in SOLVERS {
+            assert!(puzzle(solver.day).is_some(), "Day {} is missing from the catalogue", solver.day);
+        }
+    }
+
+    #[test]
+    fn params_are_known() {
+        let known: Vec<&str> = Params::default().entries().into_iter().map(|(name, _)| name).collect();
+        for (name, _) in PUZZLES.iter().flat_map(|puzzle| puzzle.params) {
+            assert!(known.contains(name), "Unknown parameter {}", name);
+        }
+        assert_eq!(PUZZLES.iter().map(|puzzle| puzzle.params.len()).sum::<usize>(), known.len());
+    }
+
+    #[test]
+    fn describes_a_puzzle() {
+        assert_eq!(puzzle(2).unwrap().to_string(), "Day 2: Red-Nosed Reports (released 2024-12-02)
+  Part 1: Count reports whose levels steadily increase or decrease [day02::count_pure_safe_reports]
+  Part 2: Count reports that are safe after removing at most one level [day02::count_dampened_safe_reports]
+  day02.max_step: Largest difference allowed between adjacent levels
+");
+    }
+}
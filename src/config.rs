@@ -0,0 +1,63 @@
+use anyhow::{bail, Context, Result};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Section {
+    pub name: String,
+    pub entries: Vec<(String, String)>,
+}
+
+impl Section {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.iter().find(|(name, _)| name == key).map(|(_, value)| value.as_str())
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    value.strip_prefix('"').and_then(|value| value.strip_suffix('"')).unwrap_or(value)
+}
+
+pub fn parse(text: &str) -> Result<Vec<Section>> {
+    let mut sections = vec![Section::default()];
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.strip_suffix(']').with_context(|| format!("Unclosed section header on line {}: {}", i + 1, line))?;
+            sections.push(Section { name: name.trim().to_string(), entries: vec![] });
+        } else if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim();
+            if key.is_empty() {
+                bail!("Missing key on line {}: {}", i + 1, line);
+            }
+            sections.last_mut().unwrap().entries.push((key.to_string(), unquote(value.trim()).to_string()));
+        } else {
+            bail!("Expected key = value on line {}: {}", i + 1, line);
+        }
+    }
+    Ok(sections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sections_and_entries() -> Result<()> {
+        let sections = parse("top = 1\n# comment\n\n[day11]\npart1_blinks = 25\n; another\nname = \"quoted value\"\n")?;
+        assert_eq!(sections, vec![
+            Section { name: "".to_string(), entries: vec![("top".to_string(), "1".to_string())] },
+            Section { name: "day11".to_string(), entries: vec![("part1_blinks".to_string(), "25".to_string()), ("name".to_string(), "quoted value".to_string())] },
+        ]);
+        assert_eq!(sections[1].get("name"), Some("quoted value"));
+        Ok(())
+    }
+
+    #[test]
+    fn reports_bad_lines() {
+        assert_eq!(parse("[day11\n").unwrap_err().to_string(), "Unclosed section header on line 1: [day11");
+        assert_eq!(parse("ok = 1\nnonsense\n").unwrap_err().to_string(), "Expected key = value on line 2: nonsense");
+        assert_eq!(parse(" = 1\n").unwrap_err().to_string(), "Missing key on line 1: = 1");
+    }
+}
@@ -0,0 +1,107 @@
+use alloc::collections::BTreeMap;
+
+/// Where a sequence of states starts repeating, and how many states each repeat takes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// Brent's algorithm over `initial, step(initial), ...`, using constant memory. The step
+/// function returns `None` when the sequence ends, in which case there is no cycle.
+pub fn brent<T: Clone + PartialEq, F: FnMut(&T) -> Option<T>>(initial: T, mut step: F) -> Option<Cycle> {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial)?;
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare)?;
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+    Some(Cycle { start, length })
+}
+
+/// Remembers every state pushed so far, reporting the cycle as soon as one repeats.
+/// Suits sequences that are produced incrementally rather than by a pure step function.
+#[derive(Clone, Debug)]
+pub struct Detector<T> {
+    seen: BTreeMap<T, usize>,
+}
+
+impl<T: Ord> Detector<T> {
+    pub fn new() -> Detector<T> {
+        Detector { seen: BTreeMap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    pub fn push(&mut self, state: T) -> Option<Cycle> {
+        let index = self.seen.len();
+        match self.seen.get(&state) {
+            Some(&start) => Some(Cycle { start, length: index - start }),
+            None => {
+                self.seen.insert(state, index);
+                None
+            }
+        }
+    }
+}
+
+impl<T: Ord> Default for Detector<T> {
+    fn default() -> Detector<T> {
+        Detector::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(&state: &u64) -> Option<u64> {
+        Some((state * state + 1) % 255)
+    }
+
+    #[test]
+    fn brent_finds_start_and_length() {
+        assert_eq!(brent(3u64, next), Some(Cycle { start: 2, length: 6 }));
+        assert_eq!(brent(0u64, |&state| Some((state + 1) % 5)), Some(Cycle { start: 0, length: 5 }));
+        assert_eq!(brent(0u64, |&state| (state < 10).then_some(state + 1)), None);
+    }
+
+    #[test]
+    fn detector_agrees_with_brent() {
+        let mut detector = Detector::new();
+        let mut state = 3u64;
+        let cycle = loop {
+            if let Some(cycle) = detector.push(state) {
+                break cycle;
+            }
+            state = next(&state).unwrap();
+        };
+        assert_eq!(Some(cycle), brent(3u64, next));
+        assert_eq!(detector.len(), 8);
+    }
+}
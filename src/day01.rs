@@ -1,21 +1,49 @@
-use anyhow::{Context, Result};
+use crate::scan;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use anyhow::{Error, Result};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+/// The two historians' location lists, read side by side one pair per line.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LocationLists {
+    pub left: Vec<u64>,
+    pub right: Vec<u64>,
+}
+
+impl FromStr for LocationLists {
+    type Err = Error;
+
+    fn from_str(data: &str) -> Result<LocationLists> {
+        let mut lists = LocationLists { left: vec![], right: vec![] };
+        for line in data.lines() {
+            let [left, right] = scan::fixed(line, " ")?;
+            lists.left.push(left);
+            lists.right.push(right);
+        }
+        Ok(lists)
+    }
+}
+
+impl Display for LocationLists {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        scan::write_joined(f, self.left.iter().zip(&self.right).map(|(left, right)| format!("{}   {}", left, right)), "\n")
+    }
+}
 
 fn load_data(data: &str) -> Result<(Vec<u64>, Vec<u64>)> {
-    let mut a = vec![];
-    let mut b = vec![];
-    for line in data.lines() {
-        let mut split = line.split_whitespace();
-        a.push(split.next().with_context(|| "Couldn't get first value")?.parse().with_context(|| "Couldn't parse first value")?);
-        b.push(split.next().with_context(|| "Couldn't get second value")?.parse().with_context(|| "Couldn't parse second value")?);
-    }
-    Ok((a, b))
+    let lists: LocationLists = data.parse()?;
+    Ok((lists.left, lists.right))
 }
 
 pub fn sum_differences(data: &str) -> Result<u64> {
     let (mut a, mut b) = load_data(data)?;
     a.sort();
     b.sort();
-    Ok(a.into_iter().zip(b.into_iter()).map(|(a, b)| a.abs_diff(b)).sum())
+    Ok(a.into_iter().zip(b).map(|(a, b)| a.abs_diff(b)).sum())
 }
 
 pub fn similarity_score(data: &str) -> Result<u64> {
@@ -27,12 +55,15 @@ pub fn similarity_score(data: &str) -> Result<u64> {
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = r"3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
+    const EXAMPLE: &str = include_str!("../res/examples/day01.txt");
+
+    #[test]
+    fn model_round_trips() -> Result<()> {
+        let lists: LocationLists = EXAMPLE.parse()?;
+        assert_eq!((lists.left[0], lists.right[0]), (3, 4));
+        assert_eq!(lists.to_string(), EXAMPLE);
+        Ok(())
+    }
 
     #[test]
     fn example_1() -> Result<()> {
@@ -46,6 +77,12 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn reports_malformed_lines() {
+        assert_eq!(sum_differences("-3   4").unwrap_err().to_string(), "Unexpected sign on unsigned number in: -3   4");
+        assert_eq!(sum_differences("3x 4").unwrap_err().to_string(), "Unexpected 'x' in: 3x 4");
+    }
+
     #[test]
     fn part_1() -> Result<()> {
         println!("Part 1: {}", sum_differences(include_str!("../res/day01.txt"))?);
@@ -1,5 +1,9 @@
 use crate::day02::State::Unsafe;
+use crate::explain::Explanation;
+use crate::scan;
 use anyhow::{Context, Result};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use State::{Decreasing, Increasing};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -9,13 +13,60 @@ enum State {
     Unsafe,
 }
 
-fn load_data(data: &str) -> Result<Vec<Vec<i8>>> {
-    data.lines().map(|line| line.split_ascii_whitespace().map(|value| value.parse().with_context(|| format!("Couldn't parse {}", value))).collect()).collect()
+/// One reactor report: a line of space separated levels.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Report {
+    pub levels: Vec<i64>,
 }
 
-fn report_safe(report: &Vec<i8>) -> bool {
-    report.iter().zip(report.iter().skip(1)).map(|(a, b)| b - a).map(|diff| {
-        if diff.abs() > 3 {
+impl FromStr for Report {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Report> {
+        Ok(Report { levels: line.split_whitespace().map(scan::number).collect::<Result<_>>()? })
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        scan::write_joined(f, &self.levels, " ")
+    }
+}
+
+/// Every report from the Red-Nosed reactor, one per line.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reports {
+    pub reports: Vec<Report>,
+}
+
+impl FromStr for Reports {
+    type Err = anyhow::Error;
+
+    fn from_str(data: &str) -> Result<Reports> {
+        let reports = data.lines().map(|line| line.parse().with_context(|| format!("Couldn't parse {}", line))).collect::<Result<_>>()?;
+        Ok(Reports { reports })
+    }
+}
+
+impl Display for Reports {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        scan::write_joined(f, &self.reports, "\n")
+    }
+}
+
+fn load_data(data: &str) -> Result<Vec<Vec<i64>>> {
+    Ok(data.parse::<Reports>()?.reports.into_iter().map(|report| report.levels).collect())
+}
+
+fn differences(report: &[i64]) -> Vec<i128> {
+    report.iter().zip(report.iter().skip(1)).map(|(&a, &b)| b as i128 - a as i128).collect()
+}
+
+fn report_safe(report: &[i64], max_step: i64) -> bool {
+    differences(report).into_iter().map(|diff| {
+        if diff.abs() > max_step as i128 {
             Unsafe
         } else if diff < 0 {
             Decreasing
@@ -31,15 +82,15 @@ fn report_safe(report: &Vec<i8>) -> bool {
     }).unwrap_or(Unsafe) != Unsafe
 }
 
-fn report_safe_dampened(report: &Vec<i8>) -> bool {
-    if report_safe(report) {
+fn report_safe_dampened(report: &[i64], max_step: i64) -> bool {
+    if report_safe(report, max_step) {
         return true;
     }
 
     for i in 0..report.len() {
-        let mut report = report.clone();
+        let mut report = report.to_vec();
         report.remove(i);
-        if report_safe(&report) {
+        if report_safe(&report, max_step) {
             return true;
         }
     }
@@ -47,46 +98,85 @@ fn report_safe_dampened(report: &Vec<i8>) -> bool {
     false
 }
 
-pub fn count_pure_safe_reports(data: &str) -> Result<usize> {
-    Ok(load_data(data)?.iter().filter(|&report| report_safe(report)).count())
+pub fn count_pure_safe_reports(data: &str, max_step: i64) -> Result<usize> {
+    Ok(load_data(data)?.iter().filter(|&report| report_safe(report, max_step)).count())
 }
 
-pub fn count_dampened_safe_reports(data: &str) -> Result<usize> {
-    Ok(load_data(data)?.iter().filter(|&report| report_safe_dampened(report)).count())
+pub fn count_dampened_safe_reports(data: &str, max_step: i64) -> Result<usize> {
+    Ok(load_data(data)?.iter().filter(|&report| report_safe_dampened(report, max_step)).count())
+}
+
+pub fn explain_safe_reports(data: &str, max_step: i64, dampened: bool) -> Result<Explanation> {
+    let mut explanation = Explanation::default();
+    for report in load_data(data)? {
+        let label = scan::Joined(&report, " ").to_string();
+        let differences = ("differences", scan::Joined(&differences(&report), " ").to_string());
+        if report_safe(&report, max_step) {
+            explanation.record(label, "safe", vec![differences]);
+        } else if let Some(i) = (0..report.len()).filter(|_| dampened).find(|&i| report_safe(&[&report[..i], &report[i + 1..]].concat(), max_step)) {
+            explanation.record(label, "safe when dampened", vec![differences, ("removed level", format!("{} at {}", report[i], i))]);
+        } else {
+            explanation.record(label, "unsafe", vec![differences]);
+        }
+    }
+    explanation.answer = (explanation.count("safe") + explanation.count("safe when dampened")).to_string();
+    Ok(explanation)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = r"7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
+    const MAX_STEP: i64 = 3;
+    const EXAMPLE: &str = include_str!("../res/examples/day02.txt");
+
+    #[test]
+    fn model_round_trips() -> Result<()> {
+        let reports: Reports = EXAMPLE.parse()?;
+        assert_eq!(reports.reports[0], Report { levels: vec![7, 6, 4, 2, 1] });
+        assert_eq!(reports.to_string(), EXAMPLE);
+        assert_eq!(format!("{:#}", "1 2\n3 x".parse::<Reports>().unwrap_err()), "Couldn't parse 3 x: Expected a number, got: x");
+        Ok(())
+    }
 
     #[test]
     fn example_1() -> Result<()> {
-        assert_eq!(count_pure_safe_reports(EXAMPLE)?, 2);
+        assert_eq!(count_pure_safe_reports(EXAMPLE, MAX_STEP)?, 2);
         Ok(())
     }
 
     #[test]
     fn example_2() -> Result<()> {
-        assert_eq!(count_dampened_safe_reports(EXAMPLE)?, 4);
+        assert_eq!(count_dampened_safe_reports(EXAMPLE, MAX_STEP)?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn explain_example() -> Result<()> {
+        let explanation = explain_safe_reports(EXAMPLE, MAX_STEP, true)?;
+        assert_eq!(explanation.answer, "4");
+        assert_eq!(explanation.records[1].to_string(), "1 2 7 8 9 => unsafe [differences: 1 5 1 1]");
+        assert_eq!(explanation.records[3].to_string(), "1 3 2 4 5 => safe when dampened [differences: 2 -1 2 1, removed level: 3 at 1]");
+        assert_eq!(explain_safe_reports(EXAMPLE, MAX_STEP, false)?.answer, "2");
+        Ok(())
+    }
+
+    #[test]
+    fn wide_levels() -> Result<()> {
+        assert_eq!(count_pure_safe_reports("-9223372036854775808 9223372036854775807\n1000 1002 1005", MAX_STEP)?, 1);
+        assert_eq!(count_pure_safe_reports("-9223372036854775808 9223372036854775807", i64::MAX)?, 0);
         Ok(())
     }
 
     #[test]
     fn part_1() -> Result<()> {
-        println!("Part 1: {}", count_pure_safe_reports(include_str!("../res/day02.txt"))?);
+        println!("Part 1: {}", count_pure_safe_reports(include_str!("../res/day02.txt"), MAX_STEP)?);
         Ok(())
     }
 
     #[test]
     fn part_2() -> Result<()> {
-        println!("Part 2: {}", count_dampened_safe_reports(include_str!("../res/day02.txt"))?);
+        println!("Part 2: {}", count_dampened_safe_reports(include_str!("../res/day02.txt"), MAX_STEP)?);
         Ok(())
     }
 }
@@ -1,41 +1,108 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use regex::Regex;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-pub fn add_all_muls(data: &str) -> Result<u64> {
-    let mut sum = 0u64;
-    let re = Regex::new(r"mul\([0-9]{1,3},[0-9]{1,3}\)")?;
-    for line in data.lines() {
-        for x in re.find_iter(line) {
-            let x = x.as_str();
-            let (a, b) = x[4..(x.len() - 1)].split_once(",").with_context(|| format!("Couldn't split mul: {}", x))?;
-            let a = a.parse::<u64>()?;
-            let b = b.parse::<u64>()?;
-            sum += a * b;
+/// An uncorrupted instruction found in the computer's memory.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Instruction {
+    Mul(u64, u64),
+    Do,
+    Dont,
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Mul(a, b) => write!(f, "mul({},{})", a, b),
+            Instruction::Do => f.write_str("do()"),
+            Instruction::Dont => f.write_str("don't()"),
         }
     }
-    Ok(sum)
 }
 
-pub fn clean_do_donts(data: &str) -> Result<String> {
-    let string = match data.find("don't()") {
-        None => data.to_string(),
-        Some(start) => {
-            let remaining = &data[(start + 7)..];
-            match remaining.find("do()") {
-                None => data[..start].to_string(),
-                Some(end) => format!("{}{}", &data[..start], clean_do_donts(&remaining[(end + 4)..])?),
+/// The instructions recovered from corrupted memory, in order. Parsing drops the corruption,
+/// so displaying a program gives the shortest memory dump that behaves the same way.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Program {
+    pub instructions: Vec<Instruction>,
+}
+
+impl FromStr for Program {
+    type Err = anyhow::Error;
+
+    fn from_str(data: &str) -> Result<Program> {
+        let re = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)|do\(\)|don't\(\)")?;
+        let mut instructions = vec![];
+        for captures in re.captures_iter(data) {
+            instructions.push(match (&captures[0], captures.get(1), captures.get(2)) {
+                (_, Some(a), Some(b)) => Instruction::Mul(a.as_str().parse()?, b.as_str().parse()?),
+                ("do()", _, _) => Instruction::Do,
+                _ => Instruction::Dont,
+            });
+        }
+        Ok(Program { instructions })
+    }
+}
+
+impl Display for Program {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.instructions.iter().try_for_each(|instruction| write!(f, "{}", instruction))
+    }
+}
+
+impl Program {
+    /// The program with `don't()` sections and the do/don't markers removed.
+    pub fn enabled(&self) -> Program {
+        let mut enabled = true;
+        let mut instructions = vec![];
+        for instruction in &self.instructions {
+            match instruction {
+                Instruction::Mul(..) if enabled => instructions.push(*instruction),
+                Instruction::Mul(..) => {}
+                Instruction::Do => enabled = true,
+                Instruction::Dont => enabled = false,
             }
         }
-    };
-    Ok(string)
+        Program { instructions }
+    }
+
+    pub fn sum_muls(&self, conditional: bool) -> u64 {
+        let program = if conditional { self.enabled() } else { self.clone() };
+        program.instructions.iter()
+            .map(|instruction| match instruction {
+                Instruction::Mul(a, b) => a * b,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+pub fn add_all_muls(data: &str) -> Result<u64> {
+    Ok(data.parse::<Program>()?.sum_muls(false))
+}
+
+pub fn clean_do_donts(data: &str) -> Result<String> {
+    Ok(data.parse::<Program>()?.enabled().to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_1: &str = r"xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-    const EXAMPLE_2: &str = r"xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+    const EXAMPLE_1: &str = include_str!("../res/examples/day03_1.txt");
+    const EXAMPLE_2: &str = include_str!("../res/examples/day03_2.txt");
+
+    #[test]
+    fn model_round_trips() -> Result<()> {
+        let program: Program = EXAMPLE_2.parse()?;
+        assert_eq!(program.to_string(), "mul(2,4)don't()mul(5,5)mul(11,8)do()mul(8,5)");
+        assert_eq!(program.to_string().parse::<Program>()?, program);
+        assert_eq!((program.sum_muls(false), program.sum_muls(true)), (161, 48));
+        Ok(())
+    }
 
     #[test]
     fn example_1() -> Result<()> {
@@ -49,6 +116,15 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn model_matches_known_answers() -> Result<()> {
+        let program: Program = include_str!("../res/day03.txt").parse()?;
+        let answers = crate::answers::Answers::load(crate::answers::DEFAULT_PATH)?;
+        assert_eq!(answers.get(3, 1), Some(program.sum_muls(false).to_string().as_str()));
+        assert_eq!(answers.get(3, 2), Some(program.sum_muls(true).to_string().as_str()));
+        Ok(())
+    }
+
     #[test]
     fn part_1() -> Result<()> {
         println!("Part 1: {}", add_all_muls(include_str!("../res/day03.txt"))?);
@@ -1,81 +1,107 @@
-fn load_data(data: &str) -> Vec<Vec<char>> {
-    data.lines().map(|line| line.chars().collect()).collect()
+use crate::scan;
+use anyhow::{bail, Result};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The elf's word search: a rectangle of letters, one row per line.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WordSearch {
+    pub letters: Vec<Vec<char>>,
 }
 
-fn e_xmas(search: &Vec<Vec<char>>, x: usize, y: usize, x_len: usize, _y_len: usize) -> u64 {
-    if x <= (x_len - 4) {
-        if search[y][x] == 'X' && search[y][x + 1] == 'M' && search[y][x + 2] == 'A' && search[y][x + 3] == 'S' {
-            return 1;
+impl FromStr for WordSearch {
+    type Err = anyhow::Error;
+
+    fn from_str(data: &str) -> Result<WordSearch> {
+        let letters: Vec<Vec<char>> = data.lines().map(|line| line.chars().collect()).collect();
+        let width = letters.first().map(Vec::len).unwrap_or(0);
+        if width == 0 {
+            bail!("Word search is empty");
+        }
+        if let Some(row) = letters.iter().position(|row| row.len() != width) {
+            bail!("Row {} of word search has {} letters, expected {}", row + 1, letters[row].len(), width);
         }
+        Ok(WordSearch { letters })
+    }
+}
+
+impl Display for WordSearch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        scan::write_joined(f, self.letters.iter().map(|row| row.iter().collect::<String>()), "\n")
+    }
+}
+
+fn load_data(data: &str) -> Result<Vec<Vec<char>>> {
+    Ok(data.parse::<WordSearch>()?.letters)
+}
+
+fn e_xmas(search: &[Vec<char>], x: usize, y: usize, x_len: usize, _y_len: usize) -> u64 {
+    if x <= (x_len - 4)
+        && search[y][x] == 'X' && search[y][x + 1] == 'M' && search[y][x + 2] == 'A' && search[y][x + 3] == 'S' {
+        return 1;
     }
     0
 }
 
-fn w_xmas(search: &Vec<Vec<char>>, x: usize, y: usize, _x_len: usize, _y_len: usize) -> u64 {
-    if x >= 3 {
-        if search[y][x] == 'X' && search[y][x - 1] == 'M' && search[y][x - 2] == 'A' && search[y][x - 3] == 'S' {
-            return 1;
-        }
+fn w_xmas(search: &[Vec<char>], x: usize, y: usize, _x_len: usize, _y_len: usize) -> u64 {
+    if x >= 3
+        && search[y][x] == 'X' && search[y][x - 1] == 'M' && search[y][x - 2] == 'A' && search[y][x - 3] == 'S' {
+        return 1;
     }
     0
 }
 
-fn n_xmas(search: &Vec<Vec<char>>, x: usize, y: usize, _x_len: usize, _y_len: usize) -> u64 {
-    if y >= 3 {
-        if search[y][x] == 'X' && search[y - 1][x] == 'M' && search[y - 2][x] == 'A' && search[y - 3][x] == 'S' {
-            return 1;
-        }
+fn n_xmas(search: &[Vec<char>], x: usize, y: usize, _x_len: usize, _y_len: usize) -> u64 {
+    if y >= 3
+        && search[y][x] == 'X' && search[y - 1][x] == 'M' && search[y - 2][x] == 'A' && search[y - 3][x] == 'S' {
+        return 1;
     }
     0
 }
 
-fn s_xmas(search: &Vec<Vec<char>>, x: usize, y: usize, _x_len: usize, y_len: usize) -> u64 {
-    if y <= (y_len - 4) {
-        if search[y][x] == 'X' && search[y + 1][x] == 'M' && search[y + 2][x] == 'A' && search[y + 3][x] == 'S' {
-            return 1;
-        }
+fn s_xmas(search: &[Vec<char>], x: usize, y: usize, _x_len: usize, y_len: usize) -> u64 {
+    if y <= (y_len - 4)
+        && search[y][x] == 'X' && search[y + 1][x] == 'M' && search[y + 2][x] == 'A' && search[y + 3][x] == 'S' {
+        return 1;
     }
     0
 }
 
-fn ne_xmas(search: &Vec<Vec<char>>, x: usize, y: usize, x_len: usize, _y_len: usize) -> u64 {
-    if x <= (x_len - 4) && y >= 3 {
-        if search[y][x] == 'X' && search[y - 1][x + 1] == 'M' && search[y - 2][x + 2] == 'A' && search[y - 3][x + 3] == 'S' {
-            return 1;
-        }
+fn ne_xmas(search: &[Vec<char>], x: usize, y: usize, x_len: usize, _y_len: usize) -> u64 {
+    if x <= (x_len - 4) && y >= 3
+        && search[y][x] == 'X' && search[y - 1][x + 1] == 'M' && search[y - 2][x + 2] == 'A' && search[y - 3][x + 3] == 'S' {
+        return 1;
     }
     0
 }
 
-fn se_xmas(search: &Vec<Vec<char>>, x: usize, y: usize, x_len: usize, y_len: usize) -> u64 {
-    if x <= (x_len - 4) && y <= (y_len - 4) {
-        if search[y][x] == 'X' && search[y + 1][x + 1] == 'M' && search[y + 2][x + 2] == 'A' && search[y + 3][x + 3] == 'S' {
-            return 1;
-        }
+fn se_xmas(search: &[Vec<char>], x: usize, y: usize, x_len: usize, y_len: usize) -> u64 {
+    if x <= (x_len - 4) && y <= (y_len - 4)
+        && search[y][x] == 'X' && search[y + 1][x + 1] == 'M' && search[y + 2][x + 2] == 'A' && search[y + 3][x + 3] == 'S' {
+        return 1;
     }
     0
 }
 
-fn sw_xmas(search: &Vec<Vec<char>>, x: usize, y: usize, _x_len: usize, y_len: usize) -> u64 {
-    if x >= 3 && y <= (y_len - 4) {
-        if search[y][x] == 'X' && search[y + 1][x - 1] == 'M' && search[y + 2][x - 2] == 'A' && search[y + 3][x - 3] == 'S' {
-            return 1;
-        }
+fn sw_xmas(search: &[Vec<char>], x: usize, y: usize, _x_len: usize, y_len: usize) -> u64 {
+    if x >= 3 && y <= (y_len - 4)
+        && search[y][x] == 'X' && search[y + 1][x - 1] == 'M' && search[y + 2][x - 2] == 'A' && search[y + 3][x - 3] == 'S' {
+        return 1;
     }
     0
 }
 
-fn nw_xmas(search: &Vec<Vec<char>>, x: usize, y: usize, _x_len: usize, _y_len: usize) -> u64 {
-    if x >= 3 && y >= 3 {
-        if search[y][x] == 'X' && search[y - 1][x - 1] == 'M' && search[y - 2][x - 2] == 'A' && search[y - 3][x - 3] == 'S' {
-            return 1;
-        }
+fn nw_xmas(search: &[Vec<char>], x: usize, y: usize, _x_len: usize, _y_len: usize) -> u64 {
+    if x >= 3 && y >= 3
+        && search[y][x] == 'X' && search[y - 1][x - 1] == 'M' && search[y - 2][x - 2] == 'A' && search[y - 3][x - 3] == 'S' {
+        return 1;
     }
     0
 }
 
-pub fn find_xmas(data: &str) -> u64 {
-    let search = load_data(data);
+pub fn find_xmas(data: &str) -> Result<u64> {
+    let search = load_data(data)?;
     let mut found = 0;
     let y_len = search.len();
     let x_len = search[0].len();
@@ -93,65 +119,67 @@ pub fn find_xmas(data: &str) -> u64 {
             }
         }
     }
-    found
+    Ok(found)
 }
 
-fn verify_x_mas(search: &Vec<Vec<char>>, x: usize, y: usize) -> bool {
+fn verify_x_mas(search: &[Vec<char>], x: usize, y: usize) -> bool {
     (search[y - 1][x - 1] == 'M' && search[y - 1][x + 1] == 'S' && search[y + 1][x - 1] == 'M' && search[y + 1][x + 1] == 'S') ||
         (search[y - 1][x - 1] == 'M' && search[y - 1][x + 1] == 'M' && search[y + 1][x - 1] == 'S' && search[y + 1][x + 1] == 'S') ||
         (search[y - 1][x - 1] == 'S' && search[y - 1][x + 1] == 'M' && search[y + 1][x - 1] == 'S' && search[y + 1][x + 1] == 'M') ||
         (search[y - 1][x - 1] == 'S' && search[y - 1][x + 1] == 'S' && search[y + 1][x - 1] == 'M' && search[y + 1][x + 1] == 'M')
 }
 
-pub fn find_x_mas(data: &str) -> u64 {
-    let search = load_data(data);
+pub fn find_x_mas(data: &str) -> Result<u64> {
+    let search = load_data(data)?;
     let mut found = 0;
     let y_len = search.len();
     let x_len = search[0].len();
     for y in 1..(y_len - 1) {
         for x in 1..(x_len - 1) {
-            if search[y][x] == 'A' {
-                if verify_x_mas(&search, x, y) {
-                    found += 1;
-                }
+            if search[y][x] == 'A' && verify_x_mas(&search, x, y) {
+                found += 1;
             }
         }
     }
-    found
+    Ok(found)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = r"MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
+    const EXAMPLE: &str = include_str!("../res/examples/day04.txt");
+
+    #[test]
+    fn example_1() -> Result<()> {
+        assert_eq!(find_xmas(EXAMPLE)?, 18);
+        Ok(())
+    }
 
     #[test]
-    fn example_1() {
-        assert_eq!(find_xmas(EXAMPLE), 18);
+    fn example_2() -> Result<()> {
+        assert_eq!(find_x_mas(EXAMPLE)?, 9);
+        Ok(())
     }
 
     #[test]
-    fn example_2() {
-        assert_eq!(find_x_mas(EXAMPLE), 9);
+    fn model_round_trips() -> Result<()> {
+        let search: WordSearch = EXAMPLE.parse()?;
+        assert_eq!(search.letters[0][..4], ['M', 'M', 'M', 'S']);
+        assert_eq!(search.to_string(), EXAMPLE);
+        assert_eq!("XMAS\nXM".parse::<WordSearch>().unwrap_err().to_string(), "Row 2 of word search has 2 letters, expected 4");
+        Ok(())
     }
 
     #[test]
-    fn part_1() {
-        println!("Part 1: {}", find_xmas(include_str!("../res/day04.txt")));
+    fn part_1() -> Result<()> {
+        println!("Part 1: {}", find_xmas(include_str!("../res/day04.txt"))?);
+        Ok(())
     }
 
     #[test]
-    fn part_2() {
-        println!("Part 2: {}", find_x_mas(include_str!("../res/day04.txt")));
+    fn part_2() -> Result<()> {
+        println!("Part 2: {}", find_x_mas(include_str!("../res/day04.txt"))?);
+        Ok(())
     }
 }
@@ -1,41 +1,72 @@
+use std::fmt::{Display, Formatter, Write};
 use std::str::FromStr;
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use crate::explain::Explanation;
+use crate::scan;
 
-#[derive(Debug)]
-struct PageOrdering {
-    before: u64,
-    after: u64,
+/// A page ordering rule: `before|after`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageOrdering {
+    pub before: u64,
+    pub after: u64,
 }
 
 impl FromStr for PageOrdering {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        let (before, after) = s.split_once("|").with_context(|| format!("couldn't split page ordering rule: {}", s))?;
-        Ok(PageOrdering {
-            before: before.parse().with_context(|| format!("couldn't parse before: {}", before))?,
-            after: after.parse().with_context(|| format!("couldn't parse after: {}", after))?,
-        })
+        let (before, after) = scan::split_pair(s, "|")?;
+        Ok(PageOrdering { before: scan::number(before)?, after: scan::number(after)? })
     }
 }
 
-fn load_data(data: &str) -> Result<(Vec<PageOrdering>, Vec<Vec<u64>>)> {
-    let mut gap = false;
-    let mut orderings = vec![];
-    let mut updates = vec![];
-    for line in data.lines() {
-        if line.is_empty() {
-            gap = true;
-        } else if !gap {
-            orderings.push(line.parse()?);
-        } else {
-            updates.push(line.split(",").map(|page| page.parse::<u64>()).collect::<Result<_, _>>()?);
+impl Display for PageOrdering {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.before, self.after)
+    }
+}
+
+/// The safety manual print queue: the ordering rules, a blank line, then one comma separated update per line.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrintQueue {
+    pub orderings: Vec<PageOrdering>,
+    pub updates: Vec<Vec<u64>>,
+}
+
+impl FromStr for PrintQueue {
+    type Err = anyhow::Error;
+
+    fn from_str(data: &str) -> Result<PrintQueue> {
+        let mut blocks = scan::blocks(data);
+        let orderings = blocks.next().unwrap_or_default().lines()
+            .map(|line| line.parse().with_context(|| format!("Couldn't parse page ordering rule: {}", line)))
+            .collect::<Result<_>>()?;
+        let updates = blocks.next().unwrap_or_default().lines()
+            .map(|line| scan::list(line, ',').with_context(|| format!("Couldn't parse update: {}", line)))
+            .collect::<Result<_>>()?;
+        if let Some(extra) = blocks.next() {
+            bail!("Unexpected section after updates: {}", extra);
         }
+        Ok(PrintQueue { orderings, updates })
+    }
+}
+
+impl Display for PrintQueue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        scan::write_joined(f, &self.orderings, "\n")?;
+        f.write_str("\n\n")?;
+        scan::write_joined(f, self.updates.iter().map(|update| scan::Joined(update, ",")), "\n")
     }
-    Ok((orderings, updates))
 }
 
-fn update_valid(update: &Vec<u64>, orderings: &Vec<PageOrdering>) -> bool {
+fn load_data(data: &str) -> Result<(Vec<PageOrdering>, Vec<Vec<u64>>)> {
+    let queue: PrintQueue = data.parse()?;
+    Ok((queue.orderings, queue.updates))
+}
+
+fn update_valid(update: &[u64], orderings: &[PageOrdering]) -> bool {
     for (i, &update_page) in update.iter().enumerate() {
         for ordering in orderings.iter().filter(|ordering| ordering.before == update_page) {
             if update.iter().take(i).any(|&prior| prior == ordering.after) {
@@ -46,7 +77,7 @@ fn update_valid(update: &Vec<u64>, orderings: &Vec<PageOrdering>) -> bool {
     true
 }
 
-fn correct_update(mut update: Vec<u64>, orderings: &Vec<PageOrdering>, mut idx: usize) -> Vec<u64> {
+fn correct_update(mut update: Vec<u64>, orderings: &[PageOrdering], mut idx: usize) -> Vec<u64> {
     while idx < update.len() {
         match orderings.iter()
             .filter(|ordering| ordering.before == update[idx])
@@ -81,38 +112,73 @@ pub fn sum_corrected_invalid_middle_pages(data: &str) -> Result<u64> {
         .sum())
 }
 
+pub fn describe(data: &str) -> Result<String> {
+    let (orderings, updates) = load_data(data)?;
+    let mut out = String::new();
+    writeln!(out, "{} ordering rules:", orderings.len())?;
+    let mut befores: Vec<u64> = vec![];
+    for ordering in orderings.iter() {
+        if !befores.contains(&ordering.before) {
+            befores.push(ordering.before);
+        }
+    }
+    for before in befores {
+        let afters: Vec<String> = orderings.iter().filter(|ordering| ordering.before == before).map(|ordering| ordering.after.to_string()).collect();
+        writeln!(out, "  {} before {}", before, afters.join(", "))?;
+    }
+    writeln!(out, "{} updates:", updates.len())?;
+    for update in updates {
+        let pages: Vec<String> = update.iter().map(|page| page.to_string()).collect();
+        if update_valid(&update, &orderings) {
+            writeln!(out, "  {} valid, middle {}", pages.join(","), update[update.len() / 2])?;
+        } else {
+            let corrected = correct_update(update, &orderings, 1);
+            let corrected_pages: Vec<String> = corrected.iter().map(|page| page.to_string()).collect();
+            writeln!(out, "  {} invalid, corrected to {}, middle {}", pages.join(","), corrected_pages.join(","), corrected[corrected.len() / 2])?;
+        }
+    }
+    Ok(out)
+}
+
+pub fn explain_updates(data: &str, corrected: bool) -> Result<Explanation> {
+    let (orderings, updates) = load_data(data)?;
+    let mut explanation = Explanation::default();
+    let mut sum = 0;
+    for update in updates {
+        let label = scan::Joined(&update, ",").to_string();
+        if update_valid(&update, &orderings) {
+            if corrected {
+                explanation.record(label, "already valid", vec![]);
+            } else {
+                sum += update[update.len() / 2];
+                explanation.record(label, "valid", vec![("middle", update[update.len() / 2].to_string())]);
+            }
+        } else if corrected {
+            let update = correct_update(update, &orderings, 1);
+            sum += update[update.len() / 2];
+            explanation.record(label, "fixed", vec![("corrected", scan::Joined(&update, ",").to_string()), ("middle", update[update.len() / 2].to_string())]);
+        } else {
+            explanation.record(label, "invalid", vec![]);
+        }
+    }
+    explanation.answer = sum.to_string();
+    Ok(explanation)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = r"47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-75,47,61,53,29
-97,61,53,29,13
-75,29,13
-75,97,47,61,53
-61,13,29
-97,13,75,29,47";
+    const EXAMPLE: &str = include_str!("../res/examples/day05.txt");
+
+    #[test]
+    fn model_round_trips() -> Result<()> {
+        let queue: PrintQueue = EXAMPLE.parse()?;
+        assert_eq!(queue.orderings[0], PageOrdering { before: 47, after: 53 });
+        assert_eq!(queue.updates[0], vec![75, 47, 61, 53, 29]);
+        assert_eq!(queue.to_string(), EXAMPLE);
+        Ok(())
+    }
 
     #[test]
     fn example_1() -> Result<()> {
@@ -126,6 +192,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn describe_example() -> Result<()> {
+        let description = describe(EXAMPLE)?;
+        assert!(description.starts_with("21 ordering rules:\n  47 before 53, 13, 61, 29\n"));
+        assert!(description.contains("  75,47,61,53,29 valid, middle 61\n"));
+        assert!(description.contains("  97,13,75,29,47 invalid, corrected to 97,75,47,29,13, middle 47\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn explain_example() -> Result<()> {
+        let explanation = explain_updates(EXAMPLE, true)?;
+        assert_eq!(explanation.answer, "123");
+        assert_eq!(explanation.count("fixed"), 3);
+        assert_eq!(explanation.records[3].to_string(), "75,97,47,61,53 => fixed [corrected: 97,75,47,61,53, middle: 47]");
+        assert_eq!(explain_updates(EXAMPLE, false)?.answer, "143");
+        Ok(())
+    }
+
+    #[test]
+    fn reports_bad_rules() {
+        assert_eq!(format!("{:#}", sum_valid_middle_pages("47-53\n\n75,47").unwrap_err()), "Couldn't parse page ordering rule: 47-53: Expected \"|\" in: 47-53");
+        assert_eq!(format!("{:#}", sum_valid_middle_pages("47|53\n\n75,x").unwrap_err()), "Couldn't parse update: 75,x: Expected a number, got: x");
+    }
+
     #[test]
     fn part_1() -> Result<()> {
         println!("Part 1: {}", sum_valid_middle_pages(include_str!("../res/day05.txt"))?);
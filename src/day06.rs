@@ -1,16 +1,16 @@
-use Direction::{East, South, West};
-use crate::day06::Direction::North;
+use anyhow::{bail, Context, Result};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use Direction::{East, North, South, West};
+use crate::bitgrid::BitGrid;
+use crate::cancel::Cancel;
+use crate::cycle::Detector;
+use crate::progress::Progress;
+use crate::scan;
+use crate::simulation::{Runner, Simulation};
 use crate::day06::GuardPath::{Exit, Looping};
-use crate::day06::MapItem::{Empty, Guard, Obstacle, Visited};
-
-#[derive(PartialEq, Clone)]
-enum MapItem {
-    Empty,
-    Obstacle,
-    Guard,
-    Visited,
-}
 
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum Direction {
     North,
     East,
@@ -18,168 +18,236 @@ enum Direction {
     West,
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 enum GuardPath {
     Exit,
     Looping,
 }
 
-fn load_map(data: &str) -> Vec<Vec<MapItem>> {
-    let mut map = vec![];
-    for line in data.lines() {
-        map.push(line.chars().map(|c| match c {
-            '.' => Empty,
-            '#' => Obstacle,
-            '^' => Guard,
-            other => panic!("Unexpected map item: {}", other),
-        }).collect());
-    }
-    map
+/// The lab map: `#` marks an obstacle and `^` the guard, who starts facing north.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LabMap {
+    pub obstacles: Vec<Vec<bool>>,
+    pub guard: (usize, usize),
 }
 
-fn locate_guard(map: &Vec<Vec<MapItem>>) -> Option<(usize, usize)> {
-    for y in 0..map.len() {
-        for x in 0..map[y].len() {
-            if map[y][x] == Guard {
-                return Some((x, y));
+impl FromStr for LabMap {
+    type Err = anyhow::Error;
+
+    fn from_str(data: &str) -> Result<LabMap> {
+        let mut obstacles = vec![];
+        let mut guard = None;
+        for (y, line) in data.lines().enumerate() {
+            let mut row = vec![];
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    '.' => row.push(false),
+                    '#' => row.push(true),
+                    '^' if guard.is_none() => {
+                        guard = Some((x, y));
+                        row.push(false);
+                    }
+                    '^' => bail!("Second guard at ({}, {})", x, y),
+                    other => bail!("Unexpected map item: {}", other),
+                }
+            }
+            if obstacles.first().is_some_and(|first: &Vec<bool>| first.len() != row.len()) {
+                bail!("Row {} of map has {} cells, expected {}", y + 1, row.len(), obstacles[0].len());
             }
+            obstacles.push(row);
         }
+        Ok(LabMap { obstacles, guard: guard.context("No guard on map")? })
     }
-    None
 }
 
-fn guard_looping(turns: &Vec<(usize, usize)>) -> bool {
-    if turns.len() > 3 {
-        let ultimate = turns.len() - 1;
-        let penultimate = turns.len() - 2;
-        for i in 0..penultimate {
-            if turns[i] == turns[penultimate] && turns[i + 1] == turns[ultimate] {
-                return true;
-            }
-        }
+impl Display for LabMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rows = self.obstacles.iter().enumerate().map(|(y, row)| {
+            row.iter().enumerate().map(|(x, &obstacle)| match obstacle {
+                _ if (x, y) == self.guard => '^',
+                true => '#',
+                false => '.',
+            }).collect::<String>()
+        });
+        scan::write_joined(f, rows, "\n")
     }
-    false
 }
 
-fn travel_map(map: &mut Vec<Vec<MapItem>>) -> GuardPath {
-    let (mut guard_x, mut guard_y) = match locate_guard(&map) {
-        None => panic!("No guard!"),
-        Some(coords) => coords,
-    };
-    let mut direction = North;
-    let mut turns = vec![];
-
-    loop {
-        map[guard_y][guard_x] = Visited;
-        match direction {
-            North => {
-                if guard_y == 0 {
-                    return Exit;
-                } else if map[guard_y - 1][guard_x] == Obstacle {
-                    turns.push((guard_x, guard_y));
-                    direction = East;
-                } else {
-                    guard_y -= 1;
-                }
-            }
-            East => {
-                if guard_x == (map[0].len() - 1) {
-                    return Exit;
-                } else if map[guard_y][guard_x + 1] == Obstacle {
-                    turns.push((guard_x, guard_y));
-                    direction = South;
-                } else {
-                    guard_x += 1;
-                }
-            }
-            South => {
-                if guard_y == (map.len() - 1) {
-                    return Exit;
-                } else if map[guard_y + 1][guard_x] == Obstacle {
-                    turns.push((guard_x, guard_y));
-                    direction = West;
-                } else {
-                    guard_y += 1;
-                }
-            }
-            West => {
-                if guard_x == 0 {
-                    return Exit;
-                } else if map[guard_y][guard_x - 1] == Obstacle {
-                    turns.push((guard_x, guard_y));
-                    direction = North;
-                } else {
-                    guard_x -= 1;
+struct Patrol {
+    obstacles: BitGrid,
+}
+
+#[derive(Clone)]
+struct Guard {
+    x: usize,
+    y: usize,
+    direction: Direction,
+    visited: BitGrid,
+    turns: Detector<(usize, usize, Direction)>,
+    path: Option<GuardPath>,
+}
+
+impl Guard {
+    fn new(lab: &LabMap) -> Guard {
+        let (x, y) = lab.guard;
+        let visited = BitGrid::new(lab.obstacles[0].len(), lab.obstacles.len());
+        Guard { x, y, direction: North, visited, turns: Detector::new(), path: None }
+    }
+}
+
+impl Simulation for Patrol {
+    type State = Guard;
+
+    fn step(&self, guard: &mut Guard) {
+        guard.visited.set(guard.x, guard.y);
+        let ahead = match guard.direction {
+            North => guard.y.checked_sub(1).map(|y| (guard.x, y)),
+            East => (guard.x + 1 < self.obstacles.width()).then_some((guard.x + 1, guard.y)),
+            South => (guard.y + 1 < self.obstacles.height()).then_some((guard.x, guard.y + 1)),
+            West => guard.x.checked_sub(1).map(|x| (x, guard.y)),
+        };
+        match ahead {
+            None => guard.path = Some(Exit),
+            Some((x, y)) if self.obstacles.test(x, y) => {
+                guard.direction = match guard.direction {
+                    North => East,
+                    East => South,
+                    South => West,
+                    West => North,
+                };
+                if guard.turns.push((guard.x, guard.y, guard.direction)).is_some() {
+                    guard.path = Some(Looping);
                 }
             }
+            Some((x, y)) => (guard.x, guard.y) = (x, y),
         }
-        if guard_looping(&turns) {
-            return Looping;
-        }
+    }
+
+    fn is_done(&self, guard: &Guard) -> bool {
+        guard.path.is_some()
     }
 }
 
-fn count_visited(map: &Vec<Vec<MapItem>>) -> usize {
-    map.iter().map(|row| row.iter().filter(|&pos| *pos == Visited).count()).sum()
+fn travel_map(lab: &LabMap, obstacles: BitGrid) -> Guard {
+    let mut runner = Runner::new(Patrol { obstacles }, Guard::new(lab));
+    runner.finish();
+    runner.into_state()
 }
 
-pub fn count_path(data: &str) -> usize {
-    let mut map = load_map(data);
-    travel_map(&mut map);
-    count_visited(&map)
+pub fn count_path(data: &str) -> Result<usize> {
+    let lab: LabMap = data.parse()?;
+    Ok(travel_map(&lab, BitGrid::from_rows(&lab.obstacles)).visited.count())
 }
 
-pub fn count_guard_loops(data: &str) -> usize {
-    let mut map = load_map(data);
-    let clean_map = map.clone();
-    travel_map(&mut map);
+pub fn render_path(data: &str) -> Result<String> {
+    let lab: LabMap = data.parse()?;
+    let guard = travel_map(&lab, BitGrid::from_rows(&lab.obstacles));
+    let mut out = String::new();
+    for (y, row) in lab.obstacles.iter().enumerate() {
+        for (x, &obstacle) in row.iter().enumerate() {
+            out.push(match obstacle {
+                _ if (x, y) == lab.guard => '^',
+                true => '#',
+                false if guard.visited.test(x, y) => 'X',
+                false => '.',
+            });
+        }
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+pub fn count_guard_loops(data: &str, cancel: &Cancel, progress: &dyn Progress) -> Result<usize> {
+    let lab: LabMap = data.parse()?;
+    let obstacles = BitGrid::from_rows(&lab.obstacles);
+    let visited = travel_map(&lab, obstacles.clone()).visited;
+    let candidates = visited.count() - 1;
+    let mut tried = 0;
     let mut loops = 0;
-    for y in 0..map.len() {
-        for x in 0..map[0].len() {
-            if map[y][x] == Visited && clean_map[y][x] != Guard {
-                let mut trial = clean_map.clone();
-                trial[y][x] = Obstacle;
-                if travel_map(&mut trial) == Looping {
-                    loops += 1;
-                }
-            }
+    for (x, y) in visited.iter().filter(|&cell| cell != lab.guard) {
+        cancel.check()?;
+        let mut trial = obstacles.clone();
+        trial.set(x, y);
+        if travel_map(&lab, trial).path == Some(Looping) {
+            loops += 1;
         }
+        tried += 1;
+        progress.report(tried, Some(candidates));
     }
-    loops
+    Ok(loops)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::progress::NoProgress;
+    use crate::snapshot::assert_snapshot;
 
-    const EXAMPLE: &str = r"....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
+    const EXAMPLE: &str = include_str!("../res/examples/day06.txt");
+
+    #[test]
+    fn example_1() -> Result<()> {
+        assert_eq!(count_path(EXAMPLE)?, 41);
+        Ok(())
+    }
+
+    #[test]
+    fn example_2() -> Result<()> {
+        assert_eq!(count_guard_loops(EXAMPLE, &Cancel::never(), &NoProgress)?, 6);
+        Ok(())
+    }
+
+    #[test]
+    fn reports_progress() -> Result<()> {
+        let reports = std::sync::Mutex::new(vec![]);
+        count_guard_loops(EXAMPLE, &Cancel::never(), &|done, total| reports.lock().unwrap().push((done, total)))?;
+        let reports = reports.into_inner().unwrap();
+        assert_eq!(reports.len(), 40);
+        assert_eq!(reports[0], (1, Some(40)));
+        assert_eq!(reports[39], (40, Some(40)));
+        Ok(())
+    }
+
+    #[test]
+    fn cancelled() {
+        let cancel = Cancel::never();
+        cancel.cancel();
+        assert!(count_guard_loops(EXAMPLE, &cancel, &NoProgress).is_err());
+    }
+
+    #[test]
+    fn model_round_trips() -> Result<()> {
+        let lab: LabMap = EXAMPLE.parse()?;
+        assert_eq!(lab.guard, (4, 6));
+        assert!(lab.obstacles[0][4]);
+        assert_eq!(lab.to_string(), EXAMPLE);
+        assert_eq!("^.\n.^".parse::<LabMap>().unwrap_err().to_string(), "Second guard at (1, 1)");
+        assert_eq!("..\n.#".parse::<LabMap>().unwrap_err().to_string(), "No guard on map");
+        Ok(())
+    }
 
     #[test]
-    fn example_1() {
-        assert_eq!(count_path(EXAMPLE), 41);
+    fn render_example() -> Result<()> {
+        assert_eq!(render_path(EXAMPLE)?, "....#.....\n....XXXXX#\n....X...X.\n..#.X...X.\n..XXXXX#X.\n..X.X.X.X.\n.#XX^XXXX.\n.XXXXXXX#.\n#XXXXXXX..\n......#X..\n");
+        Ok(())
     }
 
     #[test]
-    fn example_2() {
-        assert_eq!(count_guard_loops(EXAMPLE), 6);
+    fn path_snapshot() -> Result<()> {
+        assert_snapshot("day06_input_path", &render_path(include_str!("../res/day06.txt"))?);
+        Ok(())
     }
 
     #[test]
-    fn part_1() {
-        println!("Part 1: {}", count_path(include_str!("../res/day06.txt")));
+    fn part_1() -> Result<()> {
+        println!("Part 1: {}", count_path(include_str!("../res/day06.txt"))?);
+        Ok(())
     }
 
     #[test]
-    fn part_2() {
-        println!("Part 2: {}", count_guard_loops(include_str!("../res/day06.txt")));
+    fn part_2() -> Result<()> {
+        println!("Part 2: {}", count_guard_loops(include_str!("../res/day06.txt"), &Cancel::never(), &NoProgress)?);
+        Ok(())
     }
 }
@@ -1,95 +1,232 @@
-struct Calibration {
-    total: u64,
-    operands: Vec<u64>,
-}
-
-fn load_data(data: &str) -> Vec<Calibration> {
-    let mut calibrations = vec![];
-    for line in data.lines() {
-        match line.split_once(": ") {
-            None => panic!("Couldn't parse input line: {}", line),
-            Some((total, operands)) => {
-                calibrations.push(Calibration {
-                    total: total.parse().unwrap(),
-                    operands: operands.split_whitespace().map(|operand| operand.parse().unwrap()).collect(),
-                });
-            }
-        }
+use crate::explain::Explanation;
+use crate::scan;
+use anyhow::{Context, Result};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A calibration equation missing its operators: `total: operand operand ...`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Calibration {
+    pub total: u64,
+    pub operands: Vec<u64>,
+}
+
+impl FromStr for Calibration {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Calibration> {
+        let (total, operands) = scan::split_pair(line, ":").with_context(|| format!("Couldn't parse input line: {}", line))?;
+        let operands = scan::list(operands.trim(), ' ').with_context(|| format!("Couldn't parse operands: {}", line))?;
+        Ok(Calibration { total: scan::number(total)?, operands })
+    }
+}
+
+impl Display for Calibration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.total)?;
+        scan::write_joined(f, &self.operands, " ")
+    }
+}
+
+/// Every calibration equation, one per line.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Calibrations {
+    pub calibrations: Vec<Calibration>,
+}
+
+impl FromStr for Calibrations {
+    type Err = anyhow::Error;
+
+    fn from_str(data: &str) -> Result<Calibrations> {
+        Ok(Calibrations { calibrations: data.lines().map(str::parse).collect::<Result<_>>()? })
     }
-    calibrations
 }
 
-fn concat_operands(a: u64, b: u64) -> u64 {
-    format!("{}{}", a, b).parse().unwrap()  // I could have determined the scale and done cheaper maths, but fuck it
+impl Display for Calibrations {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        scan::write_joined(f, &self.calibrations, "\n")
+    }
+}
+
+fn load_data(data: &str) -> Result<Vec<Calibration>> {
+    Ok(data.parse::<Calibrations>()?.calibrations)
+}
+
+// Overflowing any of these means the result is already past every u64 target, so None just prunes the branch
+fn concat_operands(a: u64, b: u64) -> Option<u64> {
+    let digits = b.checked_ilog10().unwrap_or(0) + 1;
+    a.checked_mul(10u64.checked_pow(digits)?)?.checked_add(b)
 }
 
-fn solveable(target: u64, current: u64, operands: &Vec<u64>, concat: bool) -> bool {
+fn solveable(target: u64, current: u64, operands: &[u64], concat: bool) -> bool {
     if current > target {
         false
     } else if operands.is_empty() {
         current == target
     } else {
         let operand = operands[0];
-        let operands = operands[1..].to_owned();
-        if solveable(target, current + operand, &operands, concat) {
-            true
-        } else if solveable(target, current * operand, &operands, concat) {
-            true
-        } else if concat && solveable(target, concat_operands(current, operand), &operands, true) {
-            true
-        } else {
-            false
+        let operands = &operands[1..];
+        current.checked_add(operand).is_some_and(|next| solveable(target, next, operands, concat))
+            || current.checked_mul(operand).is_some_and(|next| solveable(target, next, operands, concat))
+            || (concat && concat_operands(current, operand).is_some_and(|next| solveable(target, next, operands, true)))
+    }
+}
+
+fn find_equation(target: u64, current: u64, operands: &[u64], concat: bool, equation: String) -> Option<String> {
+    if current > target {
+        None
+    } else if operands.is_empty() {
+        if current == target { Some(equation) } else { None }
+    } else {
+        let operand = operands[0];
+        current.checked_add(operand).and_then(|next| find_equation(target, next, &operands[1..], concat, format!("{} + {}", equation, operand)))
+            .or_else(|| current.checked_mul(operand).and_then(|next| find_equation(target, next, &operands[1..], concat, format!("{} * {}", equation, operand))))
+            .or_else(|| if concat { concat_operands(current, operand).and_then(|next| find_equation(target, next, &operands[1..], true, format!("{} || {}", equation, operand))) } else { None })
+    }
+}
+
+impl Calibration {
+    // The puzzle evaluates left to right from the first operand, so both searches start there
+    fn is_solveable(&self, concat: bool) -> bool {
+        self.operands.split_first().is_some_and(|(&first, rest)| solveable(self.total, first, rest, concat))
+    }
+
+    fn equation(&self, concat: bool) -> Option<String> {
+        self.operands.split_first().and_then(|(&first, rest)| find_equation(self.total, first, rest, concat, first.to_string()))
+    }
+}
+
+pub fn explain_calibrations(data: &str, concat: bool) -> Result<Explanation> {
+    let mut explanation = Explanation::default();
+    let mut total: u128 = 0;
+    for calibration in load_data(data)? {
+        let operands: Vec<String> = calibration.operands.iter().map(|operand| operand.to_string()).collect();
+        let label = format!("{}: {}", calibration.total, operands.join(" "));
+        match calibration.equation(concat) {
+            None => explanation.record(label, "unsolveable", vec![]),
+            Some(equation) => {
+                total += calibration.total as u128;
+                explanation.record(label, "solveable", vec![("equation", equation)]);
+            }
         }
     }
+    explanation.answer = total.to_string();
+    Ok(explanation)
 }
 
-pub fn total_calibration_result(data: &str) -> u64 {
-    load_data(data)
+pub fn describe(data: &str) -> Result<String> {
+    let calibrations = load_data(data)?;
+    let mut out = format!("{} calibrations:\n", calibrations.len());
+    for calibration in calibrations {
+        let operands: Vec<String> = calibration.operands.iter().map(|operand| operand.to_string()).collect();
+        let verdict = if calibration.is_solveable(false) {
+            "solveable"
+        } else if calibration.is_solveable(true) {
+            "solveable with concat"
+        } else {
+            "unsolveable"
+        };
+        out.push_str(&format!("  {}: {} {}\n", calibration.total, operands.join(" "), verdict));
+    }
+    Ok(out)
+}
+
+pub fn total_calibration_result(data: &str) -> Result<u128> {
+    Ok(load_data(data)?
         .iter()
-        .filter(|calibration| solveable(calibration.total, 0, &calibration.operands, false))
-        .map(|calibration| calibration.total)
-        .sum()
+        .filter(|calibration| calibration.is_solveable(false))
+        .map(|calibration| calibration.total as u128)
+        .sum())
 }
 
-pub fn total_calibration_with_concat_result(data: &str) -> u64 {
-    load_data(data)
+pub fn total_calibration_with_concat_result(data: &str) -> Result<u128> {
+    Ok(load_data(data)?
         .iter()
-        .filter(|calibration| solveable(calibration.total, 0, &calibration.operands, true))
-        .map(|calibration| calibration.total)
-        .sum()
+        .filter(|calibration| calibration.is_solveable(true))
+        .map(|calibration| calibration.total as u128)
+        .sum())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = r"190: 10 19
-3267: 81 40 27
-83: 17 5
-156: 15 6
-7290: 6 8 6 15
-161011: 16 10 13
-192: 17 8 14
-21037: 9 7 18 13
-292: 11 6 16 20";
+    const EXAMPLE: &str = include_str!("../res/examples/day07.txt");
+
+    #[test]
+    fn model_round_trips() -> Result<()> {
+        let calibrations: Calibrations = EXAMPLE.parse()?;
+        assert_eq!(calibrations.calibrations[1], Calibration { total: 3267, operands: vec![81, 40, 27] });
+        assert_eq!(calibrations.to_string(), EXAMPLE);
+        Ok(())
+    }
+
+    #[test]
+    fn example_1() -> Result<()> {
+        assert_eq!(total_calibration_result(EXAMPLE)?, 3749);
+        Ok(())
+    }
+
+    #[test]
+    fn example_2() -> Result<()> {
+        assert_eq!(total_calibration_with_concat_result(EXAMPLE)?, 11387);
+        Ok(())
+    }
+
+    #[test]
+    fn large_values_do_not_overflow() -> Result<()> {
+        assert_eq!(total_calibration_with_concat_result("18446744073709551615: 4294967296 4294967296 1")?, 0);
+        assert_eq!(total_calibration_result("18446744073709551615: 18446744073709551615\n18446744073709551615: 18446744073709551614 1")?, 2 * u64::MAX as u128);
+        Ok(())
+    }
 
     #[test]
-    fn example_1() {
-        assert_eq!(total_calibration_result(EXAMPLE), 3749);
+    fn reports_bad_lines() {
+        assert_eq!(format!("{:#}", total_calibration_result("190 10 19").unwrap_err()), "Couldn't parse input line: 190 10 19: Expected \":\" in: 190 10 19");
+        assert_eq!(format!("{:#}", total_calibration_result("190: 10 x").unwrap_err()), "Couldn't parse operands: 190: 10 x: Expected a number, got: x");
+        assert_eq!(total_calibration_result("190:").unwrap_err().to_string(), "Couldn't parse operands: 190:");
     }
 
     #[test]
-    fn example_2() {
-        assert_eq!(total_calibration_with_concat_result(EXAMPLE), 11387);
+    fn describe_example() -> Result<()> {
+        let description = describe(EXAMPLE)?;
+        assert!(description.starts_with("9 calibrations:\n  190: 10 19 solveable\n  3267: 81 40 27 solveable\n  83: 17 5 unsolveable\n  156: 15 6 solveable with concat\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn explain_example() -> Result<()> {
+        let explanation = explain_calibrations(EXAMPLE, true)?;
+        assert_eq!(explanation.answer, "11387");
+        assert_eq!(explanation.records[4].to_string(), "7290: 6 8 6 15 => solveable [equation: 6 * 8 || 6 * 15]");
+        assert_eq!(explain_calibrations(EXAMPLE, false)?.answer, "3749");
+        Ok(())
+    }
+
+    #[test]
+    fn explanations_agree_with_solvers_from_the_first_operand() -> Result<()> {
+        let data = "5: 3 5\n12: 5 12\n19: 10 9\n0: 0 7";
+        assert_eq!(total_calibration_result(data)?, 19);
+        assert_eq!(total_calibration_with_concat_result(data)?, 19);
+        for concat in [false, true] {
+            let explanation = explain_calibrations(data, concat)?;
+            assert_eq!(explanation.answer, "19");
+            assert_eq!(explanation.count("solveable"), 2);
+        }
+        Ok(())
     }
 
     #[test]
-    fn part_1() {
-        println!("Part 1: {}", total_calibration_result(include_str!("../res/day07.txt")));
+    fn part_1() -> Result<()> {
+        println!("Part 1: {}", total_calibration_result(include_str!("../res/day07.txt"))?);
+        Ok(())
     }
 
     #[test]
-    fn part_2() {
-        println!("Part 2: {}", total_calibration_with_concat_result(include_str!("../res/day07.txt")));
+    fn part_2() -> Result<()> {
+        println!("Part 2: {}", total_calibration_with_concat_result(include_str!("../res/day07.txt"))?);
+        Ok(())
     }
 }
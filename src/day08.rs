@@ -1,37 +1,74 @@
+use crate::scan;
+use anyhow::{bail, Result};
 use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-struct Coords {
-    x: i64,
-    y: i64,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coords {
+    pub x: i64,
+    pub y: i64,
 }
 
-struct MapSummary {
-    x: i64,
-    y: i64,
-    antennas: HashMap<u8, Vec<Coords>>,
+/// The antenna map: each letter or digit is an antenna tuned to that frequency, `.` is empty.
+/// `x` and `y` are the largest coordinates on the map; displaying a map skips any antenna outside
+/// them or on a frequency that couldn't be parsed back.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MapSummary {
+    pub x: i64,
+    pub y: i64,
+    pub antennas: HashMap<u8, Vec<Coords>>,
 }
 
-fn load_data(data: &str) -> MapSummary {
-    let mut max_x = 0usize;
-    let mut max_y = 0usize;
-    let mut antennas = HashMap::new();
-    for (y, line) in data.lines().enumerate() {
-        for (x, freq) in line.bytes().enumerate() {
-            if freq != b'.' {
-                antennas.entry(freq).or_insert_with(|| vec![]).push(Coords { x: x as i64, y: y as i64 });
+impl FromStr for MapSummary {
+    type Err = anyhow::Error;
+
+    fn from_str(data: &str) -> Result<MapSummary> {
+        let mut max_x = 0usize;
+        let mut max_y = 0usize;
+        let mut antennas = HashMap::new();
+        for (y, line) in data.lines().enumerate() {
+            if y > 0 && line.len() != max_x + 1 {
+                bail!("Row {} of map has {} cells, expected {}", y + 1, line.len(), max_x + 1);
+            }
+            for (x, freq) in line.bytes().enumerate() {
+                if freq.is_ascii_alphanumeric() {
+                    antennas.entry(freq).or_insert_with(Vec::new).push(Coords { x: x as i64, y: y as i64 });
+                } else if freq != b'.' {
+                    bail!("Unexpected map item: {}", freq as char);
+                }
+                max_x = x;
             }
-            max_x = x;
+            max_y = y;
         }
-        max_y = y;
+        Ok(MapSummary {
+            x: max_x as i64,
+            y: max_y as i64,
+            antennas,
+        })
     }
-    MapSummary {
-        x: max_x as i64,
-        y: max_y as i64,
-        antennas,
+}
+
+impl Display for MapSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let width = usize::try_from(self.x.saturating_add(1)).unwrap_or(0);
+        let height = usize::try_from(self.y.saturating_add(1)).unwrap_or(0);
+        let mut map = vec![vec![b'.'; width]; height];
+        for (&freq, group) in self.antennas.iter().filter(|(freq, _)| freq.is_ascii_alphanumeric()) {
+            for coords in group.iter().filter(|coords| (0..=self.x).contains(&coords.x) && (0..=self.y).contains(&coords.y)) {
+                map[coords.y as usize][coords.x as usize] = freq;
+            }
+        }
+        scan::write_joined(f, map.iter().map(|row| String::from_utf8_lossy(row)), "\n")
     }
 }
 
+fn load_data(data: &str) -> Result<MapSummary> {
+    data.parse()
+}
+
 fn find_antinodes(antennas: &MapSummary) -> HashSet<Coords> {
     let mut antinodes = HashSet::new();
     for group in antennas.antennas.values() {
@@ -92,48 +129,72 @@ fn find_antinodes_with_harmonics(antennas: &MapSummary) -> HashSet<Coords> {
     antinodes
 }
 
-pub fn unique_antinode_locations(data: &str) -> usize {
-    find_antinodes(&load_data(data)).len()
+pub fn antinodes(data: &str, harmonics: bool) -> Result<HashSet<Coords>> {
+    let map = load_data(data)?;
+    Ok(if harmonics { find_antinodes_with_harmonics(&map) } else { find_antinodes(&map) })
+}
+
+pub fn unique_antinode_locations(data: &str) -> Result<usize> {
+    Ok(antinodes(data, false)?.len())
 }
 
-pub fn unique_antinode_locations_with_harmonics(data: &str) -> usize {
-    find_antinodes_with_harmonics(&load_data(data)).len()
+pub fn unique_antinode_locations_with_harmonics(data: &str) -> Result<usize> {
+    Ok(antinodes(data, true)?.len())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = r"............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............";
+    const EXAMPLE: &str = include_str!("../res/examples/day08.txt");
+
+    #[test]
+    fn example_1() -> Result<()> {
+        assert_eq!(unique_antinode_locations(EXAMPLE)?, 14);
+        Ok(())
+    }
+
+    #[test]
+    fn example_2() -> Result<()> {
+        assert_eq!(unique_antinode_locations_with_harmonics(EXAMPLE)?, 34);
+        Ok(())
+    }
+
+    #[test]
+    fn model_round_trips() -> Result<()> {
+        let map: MapSummary = EXAMPLE.parse()?;
+        assert_eq!((map.x, map.y), (11, 11));
+        assert_eq!(map.antennas[&b'A'], vec![Coords { x: 6, y: 5 }, Coords { x: 8, y: 8 }, Coords { x: 9, y: 9 }]);
+        assert_eq!(map.to_string(), EXAMPLE);
+        assert_eq!("..\n.#".parse::<MapSummary>().unwrap_err().to_string(), "Unexpected map item: #");
+        Ok(())
+    }
 
     #[test]
-    fn example_1() {
-        assert_eq!(unique_antinode_locations(EXAMPLE), 14);
+    fn displays_out_of_range_models_without_panicking() {
+        let antennas = HashMap::from([(b'a', vec![Coords { x: 1, y: 0 }, Coords { x: -1, y: 0 }, Coords { x: 0, y: 5 }]), (b'#', vec![Coords { x: 0, y: 0 }])]);
+        assert_eq!(MapSummary { x: 1, y: 1, antennas: antennas.clone() }.to_string(), ".a\n..");
+        assert_eq!(MapSummary { x: -5, y: -5, antennas }.to_string(), "");
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn example_2() {
-        assert_eq!(unique_antinode_locations_with_harmonics(EXAMPLE), 34);
+    fn serializes_antinodes() -> Result<()> {
+        let mut antinodes: Vec<Coords> = antinodes(EXAMPLE, false)?.into_iter().collect();
+        antinodes.sort_by_key(|coords| (coords.y, coords.x));
+        assert_eq!(serde_json::to_string(&antinodes[..2])?, r#"[{"x":6,"y":0},{"x":11,"y":0}]"#);
+        Ok(())
     }
 
     #[test]
-    fn part_1() {
-        println!("Part 1: {}", unique_antinode_locations(include_str!("../res/day08.txt")));
+    fn part_1() -> Result<()> {
+        println!("Part 1: {}", unique_antinode_locations(include_str!("../res/day08.txt"))?);
+        Ok(())
     }
 
     #[test]
-    fn part_2() {
-        println!("Part 2: {}", unique_antinode_locations_with_harmonics(include_str!("../res/day08.txt")));
+    fn part_2() -> Result<()> {
+        println!("Part 2: {}", unique_antinode_locations_with_harmonics(include_str!("../res/day08.txt"))?);
+        Ok(())
     }
 }
@@ -1,41 +1,85 @@
 use crate::day09::Element::{File, Gap};
+use alloc::vec;
+use alloc::vec::Vec;
+use anyhow::{bail, Error, Result};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
 
-#[derive(Clone)]
-enum Element {
+/// One run of blocks on the disk, either a file or free space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Element {
     File { id: u64, length: u64 },
     Gap { length: u64 },
 }
 
-fn naive_load_data(data: &str) -> Vec<i64> {
-    let mut id = 0;
-    let mut disk = vec![];
-    for (i, value) in data.bytes().map(|byte| byte - b'0').enumerate() {
-        if i % 2 == 0 {
-            for _ in 0..value {
-                disk.push(id);
+/// The dense disk map: single digit lengths alternating between files, numbered from zero, and gaps.
+/// Displaying a map always writes a dense map that parses: lengths are capped at 9, adjacent gaps
+/// are merged, a gap separates adjacent files, and files are renumbered in order.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiskMap {
+    pub elements: Vec<Element>,
+}
+
+impl FromStr for DiskMap {
+    type Err = Error;
+
+    fn from_str(data: &str) -> Result<DiskMap> {
+        let mut id = 0;
+        let mut elements = vec![];
+        for (i, byte) in data.bytes().enumerate() {
+            if !byte.is_ascii_digit() {
+                bail!("Unexpected character in disk map at {}: {}", i, byte as char);
             }
-            id += 1;
-        } else {
-            for _ in 0..value {
-                disk.push(-1);
+            let length = (byte - b'0') as u64;
+            if i % 2 == 0 {
+                elements.push(File { id, length });
+                id += 1;
+            } else {
+                elements.push(Gap { length });
             }
         }
+        Ok(DiskMap { elements })
     }
-    disk
 }
 
-fn smart_load_data(data: &str) -> Vec<Element> {
-    let mut id = 0;
-    let mut disk = vec![];
-    for (i, length) in data.bytes().map(|byte| byte - b'0').enumerate() {
-        if i % 2 == 0 {
-            disk.push(File { id, length: length as u64 });
-            id += 1;
-        } else {
-            disk.push(Gap { length: length as u64 });
+impl Display for DiskMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut started = false;
+        let mut gap = None;
+        for element in &self.elements {
+            match element {
+                File { length, .. } => {
+                    if started {
+                        write!(f, "{}", gap.unwrap_or(0).min(9))?;
+                    }
+                    write!(f, "{}", (*length).min(9))?;
+                    started = true;
+                    gap = None;
+                }
+                Gap { length } => {
+                    if !started {
+                        f.write_str("0")?;
+                        started = true;
+                    }
+                    gap = Some(gap.unwrap_or(0) + length);
+                }
+            }
+        }
+        match gap {
+            Some(gap) => write!(f, "{}", gap.min(9)),
+            None => Ok(()),
         }
     }
-    disk
+}
+
+fn naive_load_data(data: &str) -> Result<Vec<i64>> {
+    Ok(naive_representation(smart_load_data(data)?))
+}
+
+fn smart_load_data(data: &str) -> Result<Vec<Element>> {
+    Ok(data.parse::<DiskMap>()?.elements)
 }
 
 fn naive_compact_disk(mut disk: Vec<i64>) -> Vec<i64> {
@@ -65,7 +109,7 @@ fn smart_compact_disk(mut disk: Vec<Element>) -> Vec<Element> {
     }).next().unwrap();
     for curr_id in (1..=start_id).rev() {
         for i in 0..disk.len() {
-            if let File { id, length: file_length } = disk[i].clone() {
+            if let File { id, length: file_length } = disk[i] {
                 if id == curr_id {
                     if let Some(gap_idx) = disk.iter().enumerate().take(i).filter_map(|(idx, elem)| match elem {
                         File { .. } => None,
@@ -108,37 +152,59 @@ fn checksum(disk: Vec<i64>) -> i64 {
     disk.into_iter().enumerate().filter(|(_, id)| *id >= 0).map(|(i, id)| (i as i64) * id).sum()
 }
 
-pub fn checksum_compacted_disk_naive(data: &str) -> i64 {
-    checksum(naive_compact_disk(naive_load_data(data)))
+pub fn checksum_compacted_disk_naive(data: &str) -> Result<i64> {
+    Ok(checksum(naive_compact_disk(naive_load_data(data)?)))
 }
 
-pub fn checksum_compacted_disk_smart(data: &str) -> i64 {
-    checksum(naive_representation(smart_compact_disk(smart_load_data(data))))
+pub fn checksum_compacted_disk_smart(data: &str) -> Result<i64> {
+    Ok(checksum(naive_representation(smart_compact_disk(smart_load_data(data)?))))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = r"2333133121414131402";
+    const EXAMPLE: &str = include_str!("../res/examples/day09.txt");
+
+    #[test]
+    fn example_1() -> Result<()> {
+        assert_eq!(checksum_compacted_disk_naive(EXAMPLE)?, 1928);
+        Ok(())
+    }
+
+    #[test]
+    fn example_2() -> Result<()> {
+        assert_eq!(checksum_compacted_disk_smart(EXAMPLE)?, 2858);
+        Ok(())
+    }
 
     #[test]
-    fn example_1() {
-        assert_eq!(checksum_compacted_disk_naive(EXAMPLE), 1928);
+    fn model_round_trips() -> Result<()> {
+        let disk: DiskMap = EXAMPLE.parse()?;
+        assert_eq!(disk.elements[..3], [File { id: 0, length: 2 }, Gap { length: 3 }, File { id: 1, length: 3 }]);
+        assert_eq!(disk.to_string(), EXAMPLE);
+        assert_eq!("12x4".parse::<DiskMap>().unwrap_err().to_string(), "Unexpected character in disk map at 2: x");
+        Ok(())
     }
 
     #[test]
-    fn example_2() {
-        assert_eq!(checksum_compacted_disk_smart(EXAMPLE), 2858);
+    fn displays_any_model_as_a_parseable_map() -> Result<()> {
+        let disk = DiskMap { elements: vec![Gap { length: 2 }, Gap { length: 3 }, File { id: 7, length: 12 }, File { id: 3, length: 1 }] };
+        assert_eq!(disk.to_string(), "05901");
+        assert_eq!(disk.to_string().parse::<DiskMap>()?.elements, [File { id: 0, length: 0 }, Gap { length: 5 }, File { id: 1, length: 9 }, Gap { length: 0 }, File { id: 2, length: 1 }]);
+        assert_eq!(DiskMap { elements: vec![File { id: 0, length: 1 }, Gap { length: 0 }] }.to_string(), "10");
+        Ok(())
     }
 
     #[test]
-    fn part_1() {
-        println!("Part 1: {}", checksum_compacted_disk_naive(include_str!("../res/day09.txt")));
+    fn part_1() -> Result<()> {
+        println!("Part 1: {}", checksum_compacted_disk_naive(include_str!("../res/day09.txt"))?);
+        Ok(())
     }
 
     #[test]
-    fn part_2() {
-        println!("Part 2: {}", checksum_compacted_disk_smart(include_str!("../res/day09.txt")));
+    fn part_2() -> Result<()> {
+        println!("Part 2: {}", checksum_compacted_disk_smart(include_str!("../res/day09.txt"))?);
+        Ok(())
     }
 }
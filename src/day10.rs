@@ -1,33 +1,68 @@
+use crate::scan::{self, Grid};
+use anyhow::{bail, Result};
 use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-fn load_data(data: &str) -> Vec<Vec<u8>> {
-    data.lines().map(|line| line.bytes().map(|byte| byte - b'0').collect()).collect()
+/// The topographic map of the lava island: one height digit per position. The solvers read the
+/// input in place through a [`Grid`], validated the same way; this is the owned form for building
+/// and inspecting maps.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TopographicMap {
+    pub heights: Vec<Vec<u8>>,
 }
 
-fn find_trail_end(map: &Vec<Vec<u8>>, x: usize, y: usize) -> Vec<(usize, usize)> {
-    let current = map[y][x];
+impl FromStr for TopographicMap {
+    type Err = anyhow::Error;
+
+    fn from_str(data: &str) -> Result<TopographicMap> {
+        let grid = load_data(data)?;
+        let heights = (0..grid.height()).map(|y| grid.row(y).iter().map(|byte| byte - b'0').collect()).collect();
+        Ok(TopographicMap { heights })
+    }
+}
+
+impl Display for TopographicMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        scan::write_joined(f, self.heights.iter().map(|row| row.iter().map(|height| (b'0' + height) as char).collect::<String>()), "\n")
+    }
+}
+
+fn load_data(data: &str) -> Result<Grid<'_>> {
+    let grid = Grid::parse(data)?;
+    for y in 0..grid.height() {
+        if let Some(x) = grid.row(y).iter().position(|byte| !byte.is_ascii_digit()) {
+            bail!("Expected a height at ({}, {})", x, y);
+        }
+    }
+    Ok(grid)
+}
+
+fn find_trail_end(map: &Grid, x: usize, y: usize) -> Vec<(usize, usize)> {
+    let current = map.digit(x, y).unwrap();
     if current == 9 {
         return [(x, y)].into();
     }
-    let next = current + 1;
+    let next = Some(current + 1);
     let mut ends = vec![];
-    if y > 0 && map[y - 1][x] == next {  // north
+    if y > 0 && map.digit(x, y - 1) == next {  // north
         ends.extend(find_trail_end(map, x, y - 1));
     }
-    if x < map[0].len() - 1 && map[y][x + 1] == next {  // east
+    if map.digit(x + 1, y) == next {  // east
         ends.extend(find_trail_end(map, x + 1, y));
     }
-    if y < map.len() - 1 && map[y + 1][x] == next {  // south
+    if map.digit(x, y + 1) == next {  // south
         ends.extend(find_trail_end(map, x, y + 1));
     }
-    if x > 0 && map[y][x - 1] == next {  // west
+    if x > 0 && map.digit(x - 1, y) == next {  // west
         ends.extend(find_trail_end(map, x - 1, y));
     }
     ends
 }
 
 fn unique_ends_scoring(paths: Vec<(usize, usize)>) -> usize {
-    let unique_ends: HashSet<(usize, usize)> = HashSet::from_iter(paths.into_iter());
+    let unique_ends: HashSet<(usize, usize)> = HashSet::from_iter(paths);
     unique_ends.len()
 }
 
@@ -35,27 +70,27 @@ fn unique_paths_scoring(paths: Vec<(usize, usize)>) -> usize {
     paths.len()
 }
 
-fn score_trailhead<F>(data: &str, scoring_algo: F) -> usize
+fn score_trailhead<F>(data: &str, scoring_algo: F) -> Result<usize>
 where
     F: Fn(Vec<(usize, usize)>) -> usize,
 {
-    let map = load_data(data);
+    let map = load_data(data)?;
     let mut scores = 0;
-    for y in 0..map.len() {
-        for x in 0..map[0].len() {
-            if map[y][x] == 0 {
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            if map.digit(x, y) == Some(0) {
                 scores += scoring_algo(find_trail_end(&map, x, y));
             }
         }
     }
-    scores
+    Ok(scores)
 }
 
-pub fn sum_of_trailhead_unique_ends(data: &str) -> usize {
+pub fn sum_of_trailhead_unique_ends(data: &str) -> Result<usize> {
     score_trailhead(data, unique_ends_scoring)
 }
 
-pub fn sum_of_trailhead_paths(data: &str) -> usize {
+pub fn sum_of_trailhead_paths(data: &str) -> Result<usize> {
     score_trailhead(data, unique_paths_scoring)
 }
 
@@ -63,32 +98,44 @@ pub fn sum_of_trailhead_paths(data: &str) -> usize {
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = r"89010123
-78121874
-87430965
-96549874
-45678903
-32019012
-01329801
-10456732";
+    const EXAMPLE: &str = include_str!("../res/examples/day10.txt");
+
+    #[test]
+    fn model_round_trips() -> Result<()> {
+        let map: TopographicMap = EXAMPLE.parse()?;
+        assert_eq!(map.heights[0][..4], [8, 9, 0, 1]);
+        assert_eq!(map.to_string(), EXAMPLE);
+        assert_eq!("01\n2.".parse::<TopographicMap>().unwrap_err().to_string(), "Expected a height at (1, 1)");
+        Ok(())
+    }
+
+    #[test]
+    fn example_1() -> Result<()> {
+        assert_eq!(sum_of_trailhead_unique_ends(EXAMPLE)?, 36);
+        Ok(())
+    }
 
     #[test]
-    fn example_1() {
-        assert_eq!(sum_of_trailhead_unique_ends(EXAMPLE), 36);
+    fn example_2() -> Result<()> {
+        assert_eq!(sum_of_trailhead_paths(EXAMPLE)?, 81);
+        Ok(())
     }
 
     #[test]
-    fn example_2() {
-        assert_eq!(sum_of_trailhead_paths(EXAMPLE), 81);
+    fn reports_malformed_maps() {
+        assert_eq!(sum_of_trailhead_paths("012\n34").unwrap_err().to_string(), "Row 2 of grid is shorter than 3 columns");
+        assert_eq!(sum_of_trailhead_paths("01\n2.").unwrap_err().to_string(), "Expected a height at (1, 1)");
     }
 
     #[test]
-    fn part_1() {
-        println!("Part 1: {}", sum_of_trailhead_unique_ends(include_str!("../res/day10.txt")));
+    fn part_1() -> Result<()> {
+        println!("Part 1: {}", sum_of_trailhead_unique_ends(include_str!("../res/day10.txt"))?);
+        Ok(())
     }
 
     #[test]
-    fn part_2() {
-        println!("Part 2: {}", sum_of_trailhead_paths(include_str!("../res/day10.txt")));
+    fn part_2() -> Result<()> {
+        println!("Part 2: {}", sum_of_trailhead_paths(include_str!("../res/day10.txt"))?);
+        Ok(())
     }
 }
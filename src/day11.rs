@@ -1,65 +1,172 @@
-use std::collections::HashMap;
+use crate::bigint::BigUint;
+use crate::scan;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use anyhow::{bail, Error, Result};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
 
-fn load_data(data: &str) -> Vec<u64> {
-    data.split_whitespace().map(|number| number.parse().unwrap()).collect()
+trait Count: Clone {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
 }
 
-fn count_digits(number: u64) -> u32 {
+impl Count for u128 {
+    fn zero() -> u128 {
+        0
+    }
+
+    fn one() -> u128 {
+        1
+    }
+
+    fn checked_add(&self, other: &u128) -> Option<u128> {
+        u128::checked_add(*self, *other)
+    }
+}
+
+impl Count for BigUint {
+    fn zero() -> BigUint {
+        BigUint::zero()
+    }
+
+    fn one() -> BigUint {
+        BigUint::from(1u64)
+    }
+
+    fn checked_add(&self, other: &BigUint) -> Option<BigUint> {
+        Some(self + other)
+    }
+}
+
+/// The engraved stones in their line, left to right.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stones {
+    pub stones: Vec<u128>,
+}
+
+impl FromStr for Stones {
+    type Err = Error;
+
+    fn from_str(data: &str) -> Result<Stones> {
+        Ok(Stones { stones: data.split_whitespace().map(scan::number).collect::<Result<_>>()? })
+    }
+}
+
+impl Display for Stones {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        scan::write_joined(f, &self.stones, " ")
+    }
+}
+
+fn load_data(data: &str) -> Result<Vec<u128>> {
+    Ok(data.parse::<Stones>()?.stones)
+}
+
+fn count_digits(number: u128) -> u32 {
     number.ilog10() + 1
 }
 
-fn count_stone(stone: u64, iterations: usize, seen: &mut HashMap<(u64, usize), usize>) -> usize {
+fn count_stone<T: Count>(stone: u128, iterations: usize, seen: &mut BTreeMap<(u128, usize), T>) -> Option<T> {
     if let Some(count) = seen.get(&(stone, iterations)) {
-        *count
+        Some(count.clone())
     } else {
         let count = if iterations == 0 {
-            1
+            T::one()
         } else if stone == 0 {
-            count_stone(1, iterations - 1, seen)
+            count_stone(1, iterations - 1, seen)?
         } else {
             let digits = count_digits(stone);
-            if digits % 2 == 0 {
-                let scale = 10u64.pow(digits / 2);
+            if digits.is_multiple_of(2) {
+                let scale = 10u128.pow(digits / 2);
                 let front = stone / scale;
                 let back = stone - (front * scale);
-                count_stone(front, iterations - 1, seen) + count_stone(back, iterations - 1, seen)
+                count_stone(front, iterations - 1, seen)?.checked_add(&count_stone(back, iterations - 1, seen)?)?
             } else {
-                count_stone(stone * 2024, iterations - 1, seen)
+                count_stone(stone.checked_mul(2024)?, iterations - 1, seen)?
             }
         };
-        seen.insert((stone, iterations), count);
-        count
+        seen.insert((stone, iterations), count.clone());
+        Some(count)
+    }
+}
+
+fn count_all<T: Count>(stones: &[u128], iterations: usize) -> Option<T> {
+    let mut seen = BTreeMap::new();
+    let mut total = T::zero();
+    for &stone in stones {
+        total = total.checked_add(&count_stone(stone, iterations, &mut seen)?)?;
     }
+    Some(total)
 }
 
-pub fn count_stones(data: &str, iterations: usize) -> usize {
-    let mut seen = HashMap::new();
-    load_data(data).into_iter().map(|stone| count_stone(stone, iterations, &mut seen)).sum()
+pub fn describe(data: &str) -> Result<String> {
+    let stones: Vec<String> = load_data(data)?.iter().map(|stone| stone.to_string()).collect();
+    Ok(format!("{} stones: {}\n", stones.len(), stones.join(" ")))
+}
+
+pub fn count_stones(data: &str, iterations: usize) -> Result<BigUint> {
+    let stones = load_data(data)?;
+    if let Some(total) = count_all::<u128>(&stones, iterations) {
+        return Ok(BigUint::from(total));
+    }
+    match count_all::<BigUint>(&stones, iterations) {
+        Some(total) => Ok(total),
+        None => bail!("Stone values overflowed u128 after {} blinks", iterations),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE: &str = r"125 17";
+    const EXAMPLE: &str = include_str!("../res/examples/day11.txt");
+
+    #[test]
+    fn model_round_trips() -> Result<()> {
+        let stones: Stones = EXAMPLE.parse()?;
+        assert_eq!(stones.stones, vec![125, 17]);
+        assert_eq!(stones.to_string(), EXAMPLE);
+        assert_eq!("125 x".parse::<Stones>().unwrap_err().to_string(), "Expected a number, got: x");
+        Ok(())
+    }
+
+    #[test]
+    fn example_1() -> Result<()> {
+        assert_eq!(count_stones(EXAMPLE, 25)?, BigUint::from(55312u64));
+        Ok(())
+    }
+
+    #[test]
+    fn example_2() -> Result<()> {
+        assert_eq!(count_stones(EXAMPLE, 75)?, BigUint::from(65601038650482u64));
+        Ok(())
+    }
 
     #[test]
-    fn example_1() {
-        assert_eq!(count_stones(EXAMPLE, 25), 55312);
+    fn counts_beyond_u128() -> Result<()> {
+        assert_eq!(count_stones(EXAMPLE, 250)?.to_string(), "3853523221537753206294712805660517389189500347");
+        Ok(())
     }
 
     #[test]
-    fn example_2() {
-        assert_eq!(count_stones(EXAMPLE, 75), 65601038650482);
+    fn reports_stone_overflow() {
+        assert_eq!(count_stones("340282366920938463463374607431768211455", 1).unwrap_err().to_string(), "Stone values overflowed u128 after 1 blinks");
     }
 
     #[test]
-    fn part_1() {
-        println!("Part 1: {}", count_stones(include_str!("../res/day11.txt"), 25));
+    fn part_1() -> Result<()> {
+        println!("Part 1: {}", count_stones(include_str!("../res/day11.txt"), 25)?);
+        Ok(())
     }
 
     #[test]
-    fn part_2() {
-        println!("Part 2: {}", count_stones(include_str!("../res/day11.txt"), 75));
+    fn part_2() -> Result<()> {
+        println!("Part 2: {}", count_stones(include_str!("../res/day11.txt"), 75)?);
+        Ok(())
     }
 }
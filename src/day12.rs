@@ -1,181 +1,221 @@
-const UPPER_TO_LOWER: u8 = 32;
+use crate::bitgrid::BitGrid;
+use crate::scan;
+use anyhow::{bail, Result};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The garden plot map: each capital letter is a plot growing that plant.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Garden {
+    pub plots: Vec<Vec<u8>>,
+}
+
+impl FromStr for Garden {
+    type Err = anyhow::Error;
+
+    fn from_str(data: &str) -> Result<Garden> {
+        let plots: Vec<Vec<u8>> = data.lines().map(|line| line.bytes().collect()).collect();
+        for (y, row) in plots.iter().enumerate() {
+            if row.len() != plots[0].len() {
+                bail!("Row {} of garden has {} plots, expected {}", y + 1, row.len(), plots[0].len());
+            }
+            if let Some(x) = row.iter().position(|plant| !plant.is_ascii_uppercase()) {
+                bail!("Unexpected plant at ({}, {}): {}", x, y, row[x] as char);
+            }
+        }
+        Ok(Garden { plots })
+    }
+}
 
-struct Group {
-    area: usize,
-    edges: usize,
-    corners: Vec<(usize, usize)>,
-    _plant: u8,
+impl Display for Garden {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        scan::write_joined(f, self.plots.iter().map(|row| String::from_utf8_lossy(row)), "\n")
+    }
+}
+
+/// A region of touching plots growing the same plant. A fence has as many sides as corners, so
+/// `sides` is counted from the region's corners.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Group {
+    pub area: usize,
+    pub edges: usize,
+    pub sides: usize,
+    pub plant: char,
 }
 
-fn load_data(data: &str) -> Vec<Vec<u8>> {
-    data.lines().map(|line| line.bytes().collect()).collect()
+fn load_data(data: &str) -> Result<Vec<Vec<u8>>> {
+    Ok(data.parse::<Garden>()?.plots)
 }
 
-fn explore_group(map: &mut Vec<Vec<u8>>, x: usize, y: usize) -> Group {
+fn explore_group(map: &[Vec<u8>], counted: &mut BitGrid, x: usize, y: usize) -> Group {
     let y_len = map.len();
     let x_len = map[0].len();
     let plant = map[y][x];
-    let counted = plant + UPPER_TO_LOWER;
-    map[y][x] = counted;
+    counted.set(x, y);
     let mut area = 1;
     let mut edges = 0;
-    let mut corners = vec![];
+    let mut sides = 0;
 
     // count corners
-    let w_same = x != 0 && (map[y][x - 1] == plant || map[y][x - 1] == counted);
-    let e_same = x != (x_len - 1) && (map[y][x + 1] == plant || map[y][x + 1] == counted);
-    let n_same = y != 0 && (map[y - 1][x] == plant || map[y - 1][x] == counted);
-    let s_same = y != (y_len - 1) && (map[y + 1][x] == plant || map[y + 1][x] == counted);
-    let nw_same = x != 0 && y != 0 && (map[y - 1][x - 1] == plant || map[y - 1][x - 1] == counted);
-    let ne_same = x != (x_len - 1) && y != 0 && (map[y - 1][x + 1] == plant || map[y - 1][x + 1] == counted);
-    let sw_same = x != 0 && y != (y_len - 1) && (map[y + 1][x - 1] == plant || map[y + 1][x - 1] == counted);
-    let se_same = x != (x_len - 1) && y != (y_len - 1) && (map[y + 1][x + 1] == plant || map[y + 1][x + 1] == counted);
-
-    if !w_same && !n_same {
-        corners.push((x, y));
-    } else if w_same && n_same && !nw_same {
-        corners.push((x, y));
-    }
-    if !w_same && !s_same {
-        corners.push((x, y));
-    } else if w_same && s_same && !sw_same {
-        corners.push((x, y));
-    }
-    if !e_same && !n_same {
-        corners.push((x, y));
-    } else if e_same && n_same && !ne_same {
-        corners.push((x, y));
-    }
-    if !e_same && !s_same {
-        corners.push((x, y));
-    } else if e_same && s_same && !se_same {
-        corners.push((x, y));
+    let w_same = x != 0 && map[y][x - 1] == plant;
+    let e_same = x != (x_len - 1) && map[y][x + 1] == plant;
+    let n_same = y != 0 && map[y - 1][x] == plant;
+    let s_same = y != (y_len - 1) && map[y + 1][x] == plant;
+    let nw_same = x != 0 && y != 0 && map[y - 1][x - 1] == plant;
+    let ne_same = x != (x_len - 1) && y != 0 && map[y - 1][x + 1] == plant;
+    let sw_same = x != 0 && y != (y_len - 1) && map[y + 1][x - 1] == plant;
+    let se_same = x != (x_len - 1) && y != (y_len - 1) && map[y + 1][x + 1] == plant;
+
+    if (!w_same && !n_same) || (w_same && n_same && !nw_same) {
+        sides += 1;
+    }
+    if (!w_same && !s_same) || (w_same && s_same && !sw_same) {
+        sides += 1;
+    }
+    if (!e_same && !n_same) || (e_same && n_same && !ne_same) {
+        sides += 1;
+    }
+    if (!e_same && !s_same) || (e_same && s_same && !se_same) {
+        sides += 1;
     }
 
     // count edges and explore neighbour plants
-    if x == 0 {
+    if !w_same {
         edges += 1;
-    } else if map[y][x - 1] == plant {
-        let group = explore_group(map, x - 1, y);
+    } else if !counted.test(x - 1, y) {
+        let group = explore_group(map, counted, x - 1, y);
         area += group.area;
         edges += group.edges;
-        corners.extend(group.corners);
-    } else if map[y][x - 1] != counted {
-        edges += 1;
+        sides += group.sides;
     }
 
-    if x == x_len - 1 {
+    if !e_same {
         edges += 1;
-    } else if map[y][x + 1] == plant {
-        let group = explore_group(map, x + 1, y);
+    } else if !counted.test(x + 1, y) {
+        let group = explore_group(map, counted, x + 1, y);
         area += group.area;
         edges += group.edges;
-        corners.extend(group.corners);
-    } else if map[y][x + 1] != counted {
-        edges += 1;
+        sides += group.sides;
     }
 
-    if y == 0 {
+    if !n_same {
         edges += 1;
-    } else if map[y - 1][x] == plant {
-        let group = explore_group(map, x, y - 1);
+    } else if !counted.test(x, y - 1) {
+        let group = explore_group(map, counted, x, y - 1);
         area += group.area;
         edges += group.edges;
-        corners.extend(group.corners);
-    } else if map[y - 1][x] != counted {
-        edges += 1;
+        sides += group.sides;
     }
 
-    if y == y_len - 1 {
+    if !s_same {
         edges += 1;
-    } else if map[y + 1][x] == plant {
-        let group = explore_group(map, x, y + 1);
+    } else if !counted.test(x, y + 1) {
+        let group = explore_group(map, counted, x, y + 1);
         area += group.area;
         edges += group.edges;
-        corners.extend(group.corners);
-    } else if map[y + 1][x] != counted {
-        edges += 1;
+        sides += group.sides;
     }
 
     Group {
         area,
         edges,
-        corners,
-        _plant: plant,
+        sides,
+        plant: plant as char,
     }
 }
 
-fn find_groups(map: &mut Vec<Vec<u8>>) -> Vec<Group> {
+fn find_groups(map: &[Vec<u8>]) -> Vec<Group> {
+    let mut counted = BitGrid::new(map.first().map_or(0, Vec::len), map.len());
     let mut groups = vec![];
     for y in 0..map.len() {
         for x in 0..map[0].len() {
-            if map[y][x] < b'a' {
-                groups.push(explore_group(map, x, y));
+            if !counted.test(x, y) {
+                groups.push(explore_group(map, &mut counted, x, y));
             }
         }
     }
     groups
 }
 
-pub fn total_fencing_price(data: &str) -> usize {
-    find_groups(&mut load_data(data)).into_iter().map(|group| group.area * group.edges).sum()
+pub fn garden_groups(data: &str) -> Result<Vec<Group>> {
+    Ok(find_groups(&load_data(data)?))
+}
+
+pub fn total_fencing_price(data: &str) -> Result<usize> {
+    Ok(garden_groups(data)?.into_iter().map(|group| group.area * group.edges).sum())
 }
 
-pub fn discounted_fencing_price(data: &str) -> usize {
-    let mut map = load_data(data);
-    find_groups(&mut map).into_iter().map(|group| group.area * group.corners.len()).sum()
+pub fn discounted_fencing_price(data: &str) -> Result<usize> {
+    Ok(garden_groups(data)?.into_iter().map(|group| group.area * group.sides).sum())
+}
+
+pub fn describe(data: &str) -> Result<String> {
+    let groups = garden_groups(data)?;
+    let mut out = format!("{} groups:\n", groups.len());
+    for group in groups {
+        out.push_str(&format!("  {}: area {}, perimeter {}, sides {}\n", group.plant, group.area, group.edges, group.sides));
+    }
+    Ok(out)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const EXAMPLE_A: &str = r"RRRRIICCFF
-RRRRIICCCF
-VVRRRCCFFF
-VVRCCCJFFF
-VVVVCJJCFE
-VVIVCCJJEE
-VVIIICJJEE
-MIIIIIJJEE
-MIIISIJEEE
-MMMISSJEEE";
-    const EXAMPLE_B: &str = r"AAAA
-BBCD
-BBCC
-EEEC";
-    const EXAMPLE_C: &str = r"EEEEE
-EXXXX
-EEEEE
-EXXXX
-EEEEE";
-    const EXAMPLE_D: &str = r"AAAAAA
-AAABBA
-AAABBA
-ABBAAA
-ABBAAA
-AAAAAA";
+    const EXAMPLE_A: &str = include_str!("../res/examples/day12_a.txt");
+    const EXAMPLE_B: &str = include_str!("../res/examples/day12_b.txt");
+    const EXAMPLE_C: &str = include_str!("../res/examples/day12_c.txt");
+    const EXAMPLE_D: &str = include_str!("../res/examples/day12_d.txt");
+
+    #[test]
+    fn example_1() -> Result<()> {
+        assert_eq!(total_fencing_price(EXAMPLE_A)?, 1930);
+        Ok(())
+    }
+
+    #[test]
+    fn example_2() -> Result<()> {
+        assert_eq!(discounted_fencing_price(EXAMPLE_A)?, 1206);
+        assert_eq!(discounted_fencing_price(EXAMPLE_B)?, 80);
+        assert_eq!(discounted_fencing_price(EXAMPLE_C)?, 236);
+        assert_eq!(discounted_fencing_price(EXAMPLE_D)?, 368);
+        Ok(())
+    }
+
+    #[test]
+    fn model_round_trips() -> Result<()> {
+        let garden: Garden = EXAMPLE_B.parse()?;
+        assert_eq!(garden.plots[0], b"AAAA");
+        assert_eq!(garden.to_string(), EXAMPLE_B);
+        assert_eq!("AA\nAb".parse::<Garden>().unwrap_err().to_string(), "Unexpected plant at (1, 1): b");
+        Ok(())
+    }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn example_1() {
-        assert_eq!(total_fencing_price(EXAMPLE_A), 1930);
+    fn serializes_groups() -> Result<()> {
+        let groups = garden_groups(EXAMPLE_B)?;
+        assert_eq!(serde_json::to_string(&groups[3])?, r#"{"area":1,"edges":4,"sides":4,"plant":"D"}"#);
+        Ok(())
     }
 
     #[test]
-    fn example_2() {
-        assert_eq!(discounted_fencing_price(EXAMPLE_A), 1206);
-        assert_eq!(discounted_fencing_price(EXAMPLE_B), 80);
-        assert_eq!(discounted_fencing_price(EXAMPLE_C), 236);
-        assert_eq!(discounted_fencing_price(EXAMPLE_D), 368);
+    fn describe_example() -> Result<()> {
+        assert_eq!(describe(EXAMPLE_B)?, "5 groups:\n  A: area 4, perimeter 10, sides 4\n  B: area 4, perimeter 8, sides 4\n  C: area 4, perimeter 10, sides 8\n  D: area 1, perimeter 4, sides 4\n  E: area 3, perimeter 8, sides 4\n");
+        Ok(())
     }
 
     #[test]
-    fn part_1() {
-        println!("Part 1: {}", total_fencing_price(include_str!("../res/day12.txt")));
+    fn part_1() -> Result<()> {
+        println!("Part 1: {}", total_fencing_price(include_str!("../res/day12.txt"))?);
+        Ok(())
     }
 
     #[test]
-    fn part_2() {
-        println!("Part 2: {}", discounted_fencing_price(include_str!("../res/day12.txt")));
+    fn part_2() -> Result<()> {
+        println!("Part 2: {}", discounted_fencing_price(include_str!("../res/day12.txt"))?);
+        Ok(())
     }
 }
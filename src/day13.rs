@@ -1,44 +1,219 @@
-struct Machine {
-    a_x: i64,
-    a_y: i64,
-    b_x: i64,
-    b_y: i64,
-    p_x: i64,
-    p_y: i64,
-}
-
-fn load_data(data: &str, prize_offset: i64) -> Vec<Machine> {
-    let mut machines = vec![];
-    for machine in data.split("\n\n") {
-        let mut lines = machine.splitn(3, "\n");
-        let button_a = lines.next().unwrap();
-        let button_b = lines.next().unwrap();
-        let prize = lines.next().unwrap();
-        machines.push(Machine {
-            a_x: button_a["Button A: X+".len()..button_a.find(",").unwrap()].parse().unwrap(),
-            a_y: button_a[button_a.rfind("+").unwrap()+1..].parse().unwrap(),
-            b_x: button_b["Button B: X+".len()..button_b.find(",").unwrap()].parse().unwrap(),
-            b_y: button_b[button_b.rfind("+").unwrap()+1..].parse().unwrap(),
-            p_x: prize["Prize: X=".len()..prize.find(",").unwrap()].parse::<i64>().unwrap() + prize_offset,
-            p_y: prize[prize.rfind("=").unwrap()+1..].parse::<i64>().unwrap() + prize_offset,
+use crate::explain::Explanation;
+use crate::scan;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use anyhow::{bail, Context, Error, Result};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+const BUTTON_SEPARATORS: &str = "XY+, ";
+const PRIZE_SEPARATORS: &str = "XY=, ";
+
+/// A claw machine: how far each button moves the claw, and where the prize is.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Machine {
+    pub a_x: i128,
+    pub a_y: i128,
+    pub b_x: i128,
+    pub b_y: i128,
+    pub p_x: i128,
+    pub p_y: i128,
+}
+
+impl FromStr for Machine {
+    type Err = Error;
+
+    fn from_str(block: &str) -> Result<Machine> {
+        let lines: Vec<&str> = block.lines().collect();
+        let [button_a, button_b, prize] = lines.as_slice() else {
+            bail!("Expected three lines for machine: {}", block);
+        };
+        let [a_x, a_y] = scan::record(button_a, "Button A", BUTTON_SEPARATORS)?;
+        let [b_x, b_y] = scan::record(button_b, "Button B", BUTTON_SEPARATORS)?;
+        let [p_x, p_y] = scan::record(prize, "Prize", PRIZE_SEPARATORS)?;
+        Ok(Machine { a_x, a_y, b_x, b_y, p_x, p_y })
+    }
+}
+
+impl Display for Machine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}", self.a_x, self.a_y, self.b_x, self.b_y, self.p_x, self.p_y)
+    }
+}
+
+/// Every claw machine in the arcade, separated by blank lines.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Arcade {
+    pub machines: Vec<Machine>,
+}
+
+impl FromStr for Arcade {
+    type Err = Error;
+
+    fn from_str(data: &str) -> Result<Arcade> {
+        Ok(Arcade { machines: scan::blocks(data).map(str::parse).collect::<Result<_>>()? })
+    }
+}
+
+impl Display for Arcade {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        scan::write_joined(f, &self.machines, "\n\n")
+    }
+}
+
+fn load_data(data: &str, prize_offset: i64) -> Result<Vec<Machine>> {
+    let mut machines = data.parse::<Arcade>()?.machines;
+    for machine in &mut machines {
+        let offset = |p: i128| p.checked_add(prize_offset as i128).with_context(|| format!("Prize offset overflows: {}", p));
+        machine.p_x = offset(machine.p_x)?;
+        machine.p_y = offset(machine.p_y)?;
+    }
+    Ok(machines)
+}
+
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return if a < 0 { (-a, -1, 0) } else { (a, 1, 0) };
+    }
+    let (g, x, y) = extended_gcd(b, a % b);
+    (g, y, x - (a / b) * y)
+}
+
+/// The cheapest non-negative `a`, `b` with `a * u + b * v == w`, for non-zero `u` and `v`. The
+/// solutions are `(a0 + k * da, b0 + k * db)`; the cost is linear in `k`, so the cheapest is at
+/// whichever end of the range of `k` keeping both counts non-negative is cheaper.
+fn cheapest_combination(u: i128, v: i128, w: i128) -> Option<Option<(i128, i128)>> {
+    let (g, x, y) = extended_gcd(u, v);
+    if w % g != 0 {
+        return Some(None);
+    }
+    let (a0, b0) = (x.checked_mul(w / g)?, y.checked_mul(w / g)?);
+    let (da, db) = (v / g, -(u / g));
+    let (mut lo, mut hi) = (None, None);
+    for (start, step) in [(a0, da), (b0, db)] {
+        if step > 0 {
+            lo = lo.max(Some(-start.div_euclid(step)));
+        } else {
+            let bound = start.div_euclid(-step);
+            hi = Some(hi.map_or(bound, |hi: i128| hi.min(bound)));
+        }
+    }
+    let slope = da.checked_mul(3)?.checked_add(db)?;
+    let k = match (lo, hi) {
+        (Some(lo), Some(hi)) if lo > hi => return Some(None),
+        (Some(lo), _) if slope >= 0 => lo,
+        (_, Some(hi)) => hi,
+        (Some(lo), None) => lo,
+        (None, None) => return Some(None),
+    };
+    Some(Some((a0.checked_add(k.checked_mul(da)?)?, b0.checked_add(k.checked_mul(db)?)?)))
+}
+
+/// Presses for buttons moving along the same line: every combination reaching the prize's `x`
+/// (or `y`, when neither button moves in `x`) reaches the same point, so solve along one axis.
+fn collinear_presses(machine: &Machine) -> Option<Option<(i128, i128)>> {
+    let (u, v, w) = if machine.a_x != 0 || machine.b_x != 0 {
+        (machine.a_x, machine.b_x, machine.p_x)
+    } else {
+        (machine.a_y, machine.b_y, machine.p_y)
+    };
+    Some(match (u, v) {
+        (0, 0) => (w == 0).then_some((0, 0)),
+        (u, 0) => (w % u == 0 && w / u >= 0).then(|| (w / u, 0)),
+        (0, v) => (w % v == 0 && w / v >= 0).then(|| (0, w / v)),
+        (u, v) => cheapest_combination(u, v, w)?,
+    })
+}
+
+fn presses(machine: &Machine) -> Option<Option<(i128, i128)>> {
+    let determinant = machine.a_x.checked_mul(machine.b_y)?.checked_sub(machine.a_y.checked_mul(machine.b_x)?)?;
+    let (a, b) = if determinant == 0 {
+        match collinear_presses(machine)? {
+            Some(presses) => presses,
+            None => return Some(None),
+        }
+    } else {
+        let a = machine.p_x.checked_mul(machine.b_y)?.checked_sub(machine.p_y.checked_mul(machine.b_x)?)? / determinant;
+        let b = machine.a_x.checked_mul(machine.p_y)?.checked_sub(machine.a_y.checked_mul(machine.p_x)?)? / determinant;
+        if a < 0 || b < 0 {
+            return Some(None);
+        }
+        (a, b)
+    };
+    let hits_x = machine.a_x.checked_mul(a)?.checked_add(machine.b_x.checked_mul(b)?)? == machine.p_x;
+    let hits_y = machine.a_y.checked_mul(a)?.checked_add(machine.b_y.checked_mul(b)?)? == machine.p_y;
+    Some((hits_x && hits_y).then_some((a, b)))
+}
+
+fn solve_machine(machine: &Machine) -> Result<Option<(i128, i128)>> {
+    presses(machine).with_context(|| format!("Arithmetic overflow solving machine with prize ({}, {})", machine.p_x, machine.p_y))
+}
+
+fn cost(a: i128, b: i128) -> Result<i128> {
+    a.checked_mul(3).and_then(|a| a.checked_add(b)).with_context(|| format!("Arithmetic overflow costing A x{} B x{}", a, b))
+}
+
+/// The cheapest way to win a machine's prize.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Presses {
+    pub a: i128,
+    pub b: i128,
+    pub tokens: i128,
+}
+
+pub fn presses_per_machine(data: &str, prize_offset: i64) -> Result<Vec<Option<Presses>>> {
+    let mut presses = vec![];
+    for machine in load_data(data, prize_offset)? {
+        presses.push(match solve_machine(&machine)? {
+            Some((a, b)) => Some(Presses { a, b, tokens: cost(a, b)? }),
+            None => None,
         });
     }
-    machines
+    Ok(presses)
+}
+
+pub fn min_spend_for_most_prizes(data: &str, prize_offset: i64) -> Result<i128> {
+    let mut spend: i128 = 0;
+    for presses in presses_per_machine(data, prize_offset)?.into_iter().flatten() {
+        spend = spend.checked_add(presses.tokens).context("Arithmetic overflow totalling spend")?;
+    }
+    Ok(spend)
 }
 
-pub fn min_spend_for_most_prizes(data: &str, prize_offset: i64) -> i64 {
-    let machines = load_data(data, prize_offset);
-    let mut wins = vec![];
+pub fn describe(data: &str, prize_offset: i64) -> Result<String> {
+    let machines = load_data(data, prize_offset)?;
+    let mut out = format!("{} machines:\n", machines.len());
     for machine in machines {
-        let b = (machine.a_x * machine.p_y - machine.a_y * machine.p_x) / (machine.a_x * machine.b_y - machine.a_y * machine.b_x);
-        let a = (machine.p_x - machine.b_x * b) / machine.a_x;
-        if machine.a_x * a + machine.b_x * b == machine.p_x {
-            if machine.a_y * a + machine.b_y * b == machine.p_y {
-                wins.push(a * 3 + b);
+        out.push_str(&format!("  A ({}, {}) B ({}, {}) prize ({}, {}): ", machine.a_x, machine.a_y, machine.b_x, machine.b_y, machine.p_x, machine.p_y));
+        match solve_machine(&machine)? {
+            None => out.push_str("not winnable\n"),
+            Some((a, b)) => out.push_str(&format!("A x{} B x{} costs {}\n", a, b, cost(a, b)?)),
+        }
+    }
+    Ok(out)
+}
+
+pub fn explain_prizes(data: &str, prize_offset: i64) -> Result<Explanation> {
+    let mut explanation = Explanation::default();
+    let mut spend: i128 = 0;
+    for machine in load_data(data, prize_offset)? {
+        let label = format!("A ({}, {}) B ({}, {}) prize ({}, {})", machine.a_x, machine.a_y, machine.b_x, machine.b_y, machine.p_x, machine.p_y);
+        match solve_machine(&machine)? {
+            None => explanation.record(label, "not winnable", vec![]),
+            Some((a, b)) => {
+                let tokens = cost(a, b)?;
+                spend = spend.checked_add(tokens).context("Arithmetic overflow totalling spend")?;
+                explanation.record(label, "winnable", vec![("A presses", a.to_string()), ("B presses", b.to_string()), ("tokens", tokens.to_string())]);
             }
         }
     }
-    wins.iter().sum()
+    explanation.answer = spend.to_string();
+    Ok(explanation)
 }
 
 #[cfg(test)]
@@ -46,39 +221,108 @@ mod tests {
     use super::*;
 
     const LOCATION_OFFSET: i64 = 10000000000000;
-    const EXAMPLE: &str = r"Button A: X+94, Y+34
-Button B: X+22, Y+67
-Prize: X=8400, Y=5400
+    const EXAMPLE: &str = include_str!("../res/examples/day13.txt");
+
+    #[test]
+    fn model_round_trips() -> Result<()> {
+        let arcade: Arcade = EXAMPLE.parse()?;
+        assert_eq!(arcade.machines[0], Machine { a_x: 94, a_y: 34, b_x: 22, b_y: 67, p_x: 8400, p_y: 5400 });
+        assert_eq!(arcade.to_string(), EXAMPLE);
+        Ok(())
+    }
+
+    #[test]
+    fn example_1() -> Result<()> {
+        assert_eq!(min_spend_for_most_prizes(EXAMPLE, 0)?, 480);
+        Ok(())
+    }
+
+    #[test]
+    fn example_2() -> Result<()> {
+        assert_eq!(min_spend_for_most_prizes(EXAMPLE, LOCATION_OFFSET)?, 875318608908);
+        Ok(())
+    }
 
-Button A: X+26, Y+66
-Button B: X+67, Y+21
-Prize: X=12748, Y=12176
+    #[test]
+    fn huge_offsets_stay_exact() -> Result<()> {
+        assert_eq!(min_spend_for_most_prizes("Button A: X+1, Y+0\nButton B: X+0, Y+1\nPrize: X=0, Y=0", i64::MAX)?, 4 * i64::MAX as i128);
+        Ok(())
+    }
+
+    #[test]
+    fn collinear_machines_take_the_cheapest_presses() -> Result<()> {
+        let machine = |a: (i128, i128), b: (i128, i128), p: (i128, i128)| format!("Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}", a.0, a.1, b.0, b.1, p.0, p.1);
+        assert_eq!(presses_per_machine(&machine((1, 1), (2, 2), (3, 3)), 0)?, vec![Some(Presses { a: 1, b: 1, tokens: 4 })]);
+        assert_eq!(presses_per_machine(&machine((6, 6), (1, 1), (13, 13)), 0)?, vec![Some(Presses { a: 2, b: 1, tokens: 7 })]);
+        assert_eq!(presses_per_machine(&machine((2, 2), (4, 4), (5, 5)), 0)?, vec![None]);
+        assert_eq!(presses_per_machine(&machine((-1, -1), (2, 2), (3, 3)), 0)?, vec![Some(Presses { a: 1, b: 2, tokens: 5 })]);
+        assert_eq!(presses_per_machine(&machine((1, 1), (2, 2), (3, 4)), 0)?, vec![None]);
+        assert_eq!(presses_per_machine(&machine((0, 0), (0, 3), (0, 9)), 0)?, vec![Some(Presses { a: 0, b: 3, tokens: 3 })]);
+        assert_eq!(presses_per_machine(&machine((0, 0), (0, 0), (0, 0)), 0)?, vec![Some(Presses { a: 0, b: 0, tokens: 0 })]);
+        assert_eq!(min_spend_for_most_prizes(&machine((1, 1), (2, 2), (3, 3)), LOCATION_OFFSET)?, LOCATION_OFFSET as i128 / 2 + 4);
+        Ok(())
+    }
 
-Button A: X+17, Y+86
-Button B: X+84, Y+37
-Prize: X=7870, Y=6450
+    #[test]
+    fn machines_without_a_x_movement_are_winnable() -> Result<()> {
+        let presses = presses_per_machine("Button A: X+0, Y+1\nButton B: X+1, Y+0\nPrize: X=5, Y=7", 0)?;
+        assert_eq!(presses, vec![Some(Presses { a: 7, b: 5, tokens: 26 })]);
+        Ok(())
+    }
 
-Button A: X+69, Y+23
-Button B: X+27, Y+71
-Prize: X=18641, Y=10279";
+    #[test]
+    fn negative_presses_are_not_winnable() -> Result<()> {
+        assert_eq!(presses_per_machine("Button A: X+1, Y+0\nButton B: X+0, Y+1\nPrize: X=-2, Y=3", 0)?, vec![None]);
+        assert_eq!(presses_per_machine("Button A: X+2, Y+1\nButton B: X+1, Y+1\nPrize: X=1, Y=2", 0)?, vec![None]);
+        Ok(())
+    }
+
+    #[test]
+    fn reports_malformed_machines() {
+        assert_eq!(min_spend_for_most_prizes("Button A: X+1, Y+0\nPrize: X=0, Y=0", 0).unwrap_err().to_string(), "Expected three lines for machine: Button A: X+1, Y+0\nPrize: X=0, Y=0");
+        assert_eq!(min_spend_for_most_prizes("Button A: X+1, Y+0\nButton C: X+0, Y+1\nPrize: X=0, Y=0", 0).unwrap_err().to_string(), "Expected \"Button B:\" at start of: Button C: X+0, Y+1");
+    }
+
+    #[test]
+    fn presses_example() -> Result<()> {
+        let presses = presses_per_machine(EXAMPLE, 0)?;
+        assert_eq!(presses[0], Some(Presses { a: 80, b: 40, tokens: 280 }));
+        assert_eq!(presses[1], None);
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_presses() -> Result<()> {
+        assert_eq!(serde_json::to_string(&presses_per_machine(EXAMPLE, 0)?)?, r#"[{"a":80,"b":40,"tokens":280},null,{"a":38,"b":86,"tokens":200},null]"#);
+        Ok(())
+    }
 
     #[test]
-    fn example_1() {
-        assert_eq!(min_spend_for_most_prizes(EXAMPLE, 0), 480);
+    fn describe_example() -> Result<()> {
+        let description = describe(EXAMPLE, 0)?;
+        assert!(description.starts_with("4 machines:\n  A (94, 34) B (22, 67) prize (8400, 5400): A x80 B x40 costs 280\n  A (26, 66) B (67, 21) prize (12748, 12176): not winnable\n"));
+        Ok(())
     }
 
     #[test]
-    fn example_2() {
-        assert_eq!(min_spend_for_most_prizes(EXAMPLE, LOCATION_OFFSET), 875318608908);
+    fn explain_example() -> Result<()> {
+        let explanation = explain_prizes(EXAMPLE, 0)?;
+        assert_eq!(explanation.answer, "480");
+        assert_eq!(explanation.count("winnable"), 2);
+        assert_eq!(explanation.records[2].to_string(), "A (17, 86) B (84, 37) prize (7870, 6450) => winnable [A presses: 38, B presses: 86, tokens: 200]");
+        Ok(())
     }
 
     #[test]
-    fn part_1() {
-        println!("Part 1: {}", min_spend_for_most_prizes(include_str!("../res/day13.txt"), 0));
+    fn part_1() -> Result<()> {
+        println!("Part 1: {}", min_spend_for_most_prizes(include_str!("../res/day13.txt"), 0)?);
+        Ok(())
     }
 
     #[test]
-    fn part_2() {
-        println!("Part 2: {}", min_spend_for_most_prizes(include_str!("../res/day13.txt"), LOCATION_OFFSET));
+    fn part_2() -> Result<()> {
+        println!("Part 2: {}", min_spend_for_most_prizes(include_str!("../res/day13.txt"), LOCATION_OFFSET)?);
+        Ok(())
     }
 }
@@ -1,46 +1,96 @@
-struct Robot {
-    pos_x: i64,
-    pos_y: i64,
-    vel_x: i64,
-    vel_y: i64,
+use crate::cancel::Cancel;
+use crate::progress::Progress;
+use crate::scan;
+use crate::simulation::{Runner, Simulation};
+use anyhow::{bail, Context, Result};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A bathroom security robot: `p=x,y v=dx,dy`, moving by its velocity every second.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Robot {
+    pub pos_x: i64,
+    pub pos_y: i64,
+    pub vel_x: i64,
+    pub vel_y: i64,
 }
 
-fn load_data(data: &str) -> Vec<Robot> {
-    let mut robots = vec![];
-    for line in data.lines() {
-        let (pos_x, pos_y) = line[2..line.find(" ").unwrap()].split_once(",").unwrap();
-        let pos_x = pos_x.parse::<i64>().unwrap();
-        let pos_y = pos_y.parse::<i64>().unwrap();
-        let (vel_x, vel_y) = line[(line.find("v=").unwrap() + 2)..].split_once(",").unwrap();
-        let vel_x = vel_x.parse::<i64>().unwrap();
-        let vel_y = vel_y.parse::<i64>().unwrap();
-        robots.push(Robot { pos_x, pos_y, vel_x, vel_y });
+impl FromStr for Robot {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Robot> {
+        let fields = scan::key_values(line)?;
+        let [pos_x, pos_y] = scan::fixed(fields.get("p")?, ",")?;
+        let [vel_x, vel_y] = scan::fixed(fields.get("v")?, ",")?;
+        Ok(Robot { pos_x, pos_y, vel_x, vel_y })
     }
-    robots
 }
 
-fn move_robots(robots: &mut Vec<Robot>, len_x: i64, len_y: i64) {
-    for robot in robots {
-        robot.pos_x += robot.vel_x;
-        if robot.pos_x < 0 {
-            robot.pos_x += len_x;
-        } else if robot.pos_x >= len_x {
-            robot.pos_x -= len_x;
-        }
-        robot.pos_y += robot.vel_y;
-        if robot.pos_y < 0 {
-            robot.pos_y += len_y;
-        } else if robot.pos_y >= len_y {
-            robot.pos_y -= len_y;
+impl Display for Robot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "p={},{} v={},{}", self.pos_x, self.pos_y, self.vel_x, self.vel_y)
+    }
+}
+
+/// Every robot in the bathroom, one per line.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Robots {
+    pub robots: Vec<Robot>,
+}
+
+impl FromStr for Robots {
+    type Err = anyhow::Error;
+
+    fn from_str(data: &str) -> Result<Robots> {
+        let robots = data.lines().map(|line| line.parse().with_context(|| format!("Couldn't parse robot: {}", line))).collect::<Result<_>>()?;
+        Ok(Robots { robots })
+    }
+}
+
+impl Display for Robots {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        scan::write_joined(f, &self.robots, "\n")
+    }
+}
+
+fn load_data(data: &str) -> Result<Vec<Robot>> {
+    Ok(data.parse::<Robots>()?.robots)
+}
+
+struct Bathroom {
+    len_x: i64,
+    len_y: i64,
+}
+
+impl Simulation for Bathroom {
+    type State = Vec<Robot>;
+
+    fn step(&self, robots: &mut Vec<Robot>) {
+        for robot in robots {
+            robot.pos_x = (robot.pos_x + robot.vel_x.rem_euclid(self.len_x)).rem_euclid(self.len_x);
+            robot.pos_y = (robot.pos_y + robot.vel_y.rem_euclid(self.len_y)).rem_euclid(self.len_y);
         }
     }
 }
 
-fn robots_in_area(robots: &Vec<Robot>, start_x: i64, end_x: i64, start_y: i64, end_y: i64) -> usize {
+fn simulate<'a>(data: &str, len_x: i64, len_y: i64) -> Result<Runner<'a, Bathroom>> {
+    if len_x <= 0 || len_y <= 0 {
+        bail!("Expected a positive map size, got {}x{}", len_x, len_y);
+    }
+    let robots = load_data(data)?;
+    if let Some(robot) = robots.iter().find(|robot| !(0..len_x).contains(&robot.pos_x) || !(0..len_y).contains(&robot.pos_y)) {
+        bail!("Robot {} starts outside the {}x{} map", robot, len_x, len_y);
+    }
+    Ok(Runner::new(Bathroom { len_x, len_y }, robots))
+}
+
+fn robots_in_area(robots: &[Robot], start_x: i64, end_x: i64, start_y: i64, end_y: i64) -> usize {
     robots.iter().filter(|robot| robot.pos_x >= start_x && robot.pos_x <= end_x && robot.pos_y >= start_y && robot.pos_y <= end_y).count()
 }
 
-fn plot_map(robots: &Vec<Robot>, len_x: i64, len_y: i64) -> Vec<Vec<usize>> {
+fn plot_map(robots: &[Robot], len_x: i64, len_y: i64) -> Vec<Vec<usize>> {
     let mut map = vec![];
     for y in 0..(len_y as usize) {
         map.push(vec![]);
@@ -54,89 +104,162 @@ fn plot_map(robots: &Vec<Robot>, len_x: i64, len_y: i64) -> Vec<Vec<usize>> {
     map
 }
 
-fn print_map(map: &Vec<Vec<usize>>) {
-    for y in 0..map.len() {
-        for x in 0..map[0].len() {
-            if map[y][x] == 0 {
-                print!(".", );
+fn render_map(map: &[Vec<usize>]) -> String {
+    let mut out = String::new();
+    for row in map {
+        for &count in row {
+            if count == 0 {
+                out.push('.');
             } else {
-                print!("{}", map[y][x]);
+                out.push_str(&count.to_string());
             }
         }
-        println!();
+        out.push('\n');
     }
+    out
 }
 
-pub fn safety_factor(data: &str, len_x: i64, len_y: i64, seconds: usize) -> usize {
-    let mut robots = load_data(data);
-    for _ in 0..seconds {
-        move_robots(&mut robots, len_x, len_y);
+pub fn safety_factor(data: &str, len_x: i64, len_y: i64, seconds: usize) -> Result<usize> {
+    let mut runner = simulate(data, len_x, len_y)?;
+    runner.run(seconds);
+    let robots = runner.state();
+    Ok(robots_in_area(robots, 0, len_x / 2 - 1, 0, len_y / 2 - 1)
+        * robots_in_area(robots, len_x / 2 + 1, len_x - 1, 0, len_y / 2 - 1)
+        * robots_in_area(robots, 0, len_x / 2 - 1, len_y / 2 + 1, len_y - 1)
+        * robots_in_area(robots, len_x / 2 + 1, len_x - 1, len_y / 2 + 1, len_y - 1))
+}
+
+pub fn describe(data: &str) -> Result<String> {
+    let robots = load_data(data)?;
+    let mut out = format!("{} robots:\n", robots.len());
+    for robot in robots {
+        out.push_str(&format!("  p=({}, {}) v=({}, {})\n", robot.pos_x, robot.pos_y, robot.vel_x, robot.vel_y));
     }
-    robots_in_area(&robots, 0, len_x / 2 - 1, 0, len_y / 2 - 1)
-        * robots_in_area(&robots, len_x / 2 + 1, len_x - 1, 0, len_y / 2 - 1)
-        * robots_in_area(&robots, 0, len_x / 2 - 1, len_y / 2 + 1, len_y - 1)
-        * robots_in_area(&robots, len_x / 2 + 1, len_x - 1, len_y / 2 + 1, len_y - 1)
+    Ok(out)
 }
 
-pub fn find_picture(data: &str, len_x: i64, len_y: i64) -> usize {
-    let mut robots = load_data(data);
-    let mut seconds = 0;
-    loop {
-        seconds += 1;
-        move_robots(&mut robots, len_x, len_y);
-        let map = plot_map(&robots, len_x, len_y);
-        for y in 0..(len_y as usize) {
+pub fn render_robots(data: &str, len_x: i64, len_y: i64, seconds: usize) -> Result<String> {
+    let mut runner = simulate(data, len_x, len_y)?;
+    runner.run(seconds);
+    Ok(render_map(&plot_map(runner.state(), len_x, len_y)))
+}
+
+pub fn find_picture(data: &str, len_x: i64, len_y: i64, cancel: &Cancel, progress: &dyn Progress) -> Result<usize> {
+    let runner = simulate(data, len_x, len_y)?;
+    // Every robot is back where it started after len_x * len_y seconds, so no picture shows after that
+    let period = usize::try_from(len_x).ok().and_then(|len_x| len_x.checked_mul(len_y as usize)).context("Map is too large to search")?;
+    let mut runner = runner.on_frame(|seconds, _| progress.report(seconds, Some(period)));
+    while runner.steps() < period {
+        cancel.check()?;
+        runner.step();
+        let seconds = runner.steps();
+        for row in plot_map(runner.state(), len_x, len_y) {
             let mut in_a_row = 0;
-            for x in 0..(len_x as usize) {
-                if map[y][x] != 0 {
+            for count in row {
+                if count != 0 {
                     in_a_row += 1;
                 } else {
                     in_a_row = 0;
                 }
                 if in_a_row > 20 {
-                    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-                    print_map(&map);
-                    println!("{} seconds elapsed", seconds);
-                    return seconds;
+                    return Ok(seconds);
                 }
             }
         }
     }
+    bail!("No picture within {} seconds, after which the robots repeat", period)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::progress::NoProgress;
+    use crate::snapshot::assert_snapshot;
 
     const MAP_X: i64 = 101;
     const MAP_Y: i64 = 103;
     const EXAMPLE_X: i64 = 11;
     const EXAMPLE_Y: i64 = 7;
-    const EXAMPLE: &str = r"p=0,4 v=3,-3
-p=6,3 v=-1,-3
-p=10,3 v=-1,2
-p=2,0 v=2,-1
-p=0,0 v=1,3
-p=3,0 v=-2,-2
-p=7,6 v=-1,-3
-p=3,0 v=-1,-2
-p=9,3 v=2,3
-p=7,3 v=-1,2
-p=2,4 v=2,-3
-p=9,5 v=-3,-3";
+    const EXAMPLE: &str = include_str!("../res/examples/day14.txt");
+
+    #[test]
+    fn model_round_trips() -> Result<()> {
+        let robots: Robots = EXAMPLE.parse()?;
+        assert_eq!(robots.robots[0], Robot { pos_x: 0, pos_y: 4, vel_x: 3, vel_y: -3 });
+        assert_eq!(robots.to_string(), EXAMPLE);
+        Ok(())
+    }
+
+    #[test]
+    fn example_1() -> Result<()> {
+        assert_eq!(safety_factor(EXAMPLE, EXAMPLE_X, EXAMPLE_Y, 100)?, 12);
+        Ok(())
+    }
+
+    #[test]
+    fn render_example() -> Result<()> {
+        assert_eq!(render_robots(EXAMPLE, EXAMPLE_X, EXAMPLE_Y, 100)?, "......2..1.\n...........\n1..........\n.11........\n.....1.....\n...12......\n.1....1....\n");
+        Ok(())
+    }
+
+    #[test]
+    fn reports_malformed_robots() {
+        assert_eq!(format!("{:#}", safety_factor("p=0,4 v=3,-3\np=6,3 w=-1,-3", 11, 7, 1).unwrap_err()), "Couldn't parse robot: p=6,3 w=-1,-3: Missing v= in: p=6,3 w=-1,-3");
+        assert_eq!(format!("{:#}", safety_factor("p=0 v=3,-3", 11, 7, 1).unwrap_err()), "Couldn't parse robot: p=0 v=3,-3: Expected 2 numbers in: 0");
+    }
+
+    #[test]
+    fn fast_robots_wrap_around_the_map() -> Result<()> {
+        assert_eq!(render_robots("p=1,1 v=-25,31", 4, 3, 1)?, "....\n....\n1...\n");
+        assert_eq!(render_robots("p=0,0 v=9223372036854775807,-9223372036854775808", 4, 3, 2)?, "....\n....\n..1.\n");
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_robots_outside_the_map() {
+        assert_eq!(render_robots("p=4,0 v=1,1", 4, 3, 0).unwrap_err().to_string(), "Robot p=4,0 v=1,1 starts outside the 4x3 map");
+        assert_eq!(safety_factor("p=0,-1 v=1,1", 4, 3, 1).unwrap_err().to_string(), "Robot p=0,-1 v=1,1 starts outside the 4x3 map");
+        assert_eq!(find_picture("", 0, -3, &Cancel::never(), &NoProgress).unwrap_err().to_string(), "Expected a positive map size, got 0x-3");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_robots() -> Result<()> {
+        let robots: Robots = "p=0,4 v=3,-3\np=6,3 v=-1,-3".parse()?;
+        let json = serde_json::to_string(&robots)?;
+        assert_eq!(json, r#"{"robots":[{"pos_x":0,"pos_y":4,"vel_x":3,"vel_y":-3},{"pos_x":6,"pos_y":3,"vel_x":-1,"vel_y":-3}]}"#);
+        assert_eq!(serde_json::from_str::<Robots>(&json)?, robots);
+        Ok(())
+    }
+
+    #[test]
+    fn part_1() -> Result<()> {
+        println!("Part 1: {}", safety_factor(include_str!("../res/day14.txt"), MAP_X, MAP_Y, 100)?);
+        Ok(())
+    }
+
+    #[test]
+    fn picture_search_times_out() {
+        let cancel = Cancel::with_timeout(std::time::Duration::ZERO);
+        assert_eq!(find_picture(EXAMPLE, EXAMPLE_X, EXAMPLE_Y, &cancel, &NoProgress).unwrap_err().to_string(), "Solver timed out");
+    }
 
     #[test]
-    fn example_1() {
-        assert_eq!(safety_factor(EXAMPLE, EXAMPLE_X, EXAMPLE_Y, 100), 12);
+    fn picture_search_stops_when_robots_repeat() {
+        assert_eq!(find_picture(EXAMPLE, EXAMPLE_X, EXAMPLE_Y, &Cancel::never(), &NoProgress).unwrap_err().to_string(), "No picture within 77 seconds, after which the robots repeat");
     }
 
     #[test]
-    fn part_1() {
-        println!("Part 1: {}", safety_factor(include_str!("../res/day14.txt"), MAP_X, MAP_Y, 100));
+    fn picture_snapshot() -> Result<()> {
+        let data = include_str!("../res/day14.txt");
+        let seconds = find_picture(data, MAP_X, MAP_Y, &Cancel::never(), &NoProgress)?;
+        assert_snapshot("day14_input_picture", &render_robots(data, MAP_X, MAP_Y, seconds)?);
+        Ok(())
     }
 
     #[test]
-    fn part_2() {
-        println!("Part 2: {}", find_picture(include_str!("../res/day14.txt"), MAP_X, MAP_Y));
+    fn part_2() -> Result<()> {
+        println!("Part 2: {}", find_picture(include_str!("../res/day14.txt"), MAP_X, MAP_Y, &Cancel::never(), &NoProgress)?);
+        Ok(())
     }
 }
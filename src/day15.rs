@@ -1,10 +1,16 @@
+use crate::scan;
+use crate::simulation::{Runner, Simulation};
+use anyhow::{bail, Context, Result};
 use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
 use MapItem::Carton;
 use crate::day15::Instruction::{Down, Left, Right, Up};
 use crate::day15::MapItem::{CartonL, CartonR, Nothing, Robot, Wall};
 
-#[derive(Copy, Clone, PartialEq)]
-enum MapItem {
+/// One cell of the warehouse. `CartonL` and `CartonR` are the two halves of a carton in the widened warehouse.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MapItem {
     Robot,
     Wall,
     Carton,
@@ -26,8 +32,9 @@ impl Display for MapItem {
     }
 }
 
-#[derive(Copy, Clone, PartialEq)]
-enum Instruction {
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Instruction {
     Up,
     Down,
     Left,
@@ -45,88 +52,124 @@ impl Display for Instruction {
     }
 }
 
-fn load_data(data: &str, wide: bool) -> ((usize, usize), Vec<Vec<MapItem>>, Vec<Instruction>) {
-    let (map_data, instruction_data) = data.split_once("\n\n").unwrap();
-    let mut robot_loc = None;
-    let mut map = vec![];
-    for (y, line) in map_data.lines().enumerate() {
-        let mut row = vec![];
-        for (x, b) in line.bytes().enumerate() {
-            let item = match b {
-                b'#' => Wall,
-                b'@' => Robot,
-                b'O' => Carton,
-                b'.' => Nothing,
-                other => panic!("Unknown item in map: {}", other as char),  
-            };
-            if wide {
-                let (item_l, item_r) = match item {
-                    Robot => {
-                        robot_loc = Some((x * 2, y));
-                        (Robot, Nothing) 
-                    }
-                    Wall => (Wall, Wall),
-                    Carton => (CartonL, CartonR),
-                    Nothing => (Nothing, Nothing),
-                    _ => panic!("Shouldn't be reachable"),
-                };
-                row.push(item_l);
-                row.push(item_r);
-            } else {
-                if item == Robot {
-                    robot_loc = Some((x, y));
-                }
-                row.push(item);
-            }
+/// The warehouse map, a blank line, then the robot's moves. Moves are kept in their input lines
+/// so the puzzle text round-trips.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Warehouse {
+    pub map: Vec<Vec<MapItem>>,
+    pub moves: Vec<Vec<Instruction>>,
+}
+
+impl FromStr for Warehouse {
+    type Err = anyhow::Error;
+
+    fn from_str(data: &str) -> Result<Warehouse> {
+        let mut blocks = scan::blocks(data);
+        let (Some(map_data), Some(move_data), None) = (blocks.next(), blocks.next(), blocks.next()) else {
+            bail!("Expected a map and a list of moves separated by a blank line");
+        };
+        let map: Vec<Vec<MapItem>> = map_data.lines().map(|line| line.bytes().map(|b| match b {
+            b'#' => Ok(Wall),
+            b'@' => Ok(Robot),
+            b'O' => Ok(Carton),
+            b'.' => Ok(Nothing),
+            other => bail!("Unknown item in map: {}", other as char),
+        }).collect()).collect::<Result<_>>()?;
+        if map.iter().flatten().filter(|&&item| item == Robot).count() != 1 {
+            bail!("Expected exactly one robot in map");
         }
-        map.push(row);
+        let moves = move_data.lines().map(|line| line.bytes().map(|b| match b {
+            b'^' => Ok(Up),
+            b'>' => Ok(Right),
+            b'v' => Ok(Down),
+            b'<' => Ok(Left),
+            other => bail!("Unknown item in instructions: {}", other as char),
+        }).collect()).collect::<Result<_>>()?;
+        Ok(Warehouse { map, moves })
     }
-    let instructions = instruction_data.bytes().filter(|&b| b != b'\n').map(|b| match b {
-        b'^' => Up,
-        b'>' => Right,
-        b'v' => Down,
-        b'<' => Left,
-        other => panic!("Unknown item in instructions: {}", other as char),
-    }).collect();
-    (robot_loc.unwrap(), map, instructions)
 }
 
-#[allow(dead_code)]
-fn print_map(map: &Vec<Vec<MapItem>>) {
+impl Display for Warehouse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(render_map(&self.map).trim_end())?;
+        f.write_str("\n\n")?;
+        scan::write_joined(f, self.moves.iter().map(|line| line.iter().map(Instruction::to_string).collect::<String>()), "\n")
+    }
+}
+
+fn widen(map: Vec<Vec<MapItem>>) -> Vec<Vec<MapItem>> {
+    map.into_iter().map(|row| row.into_iter().flat_map(|item| match item {
+        Robot => [Robot, Nothing],
+        Carton => [CartonL, CartonR],
+        other => [other, other],
+    }).collect()).collect()
+}
+
+type Setup = ((usize, usize), Vec<Vec<MapItem>>, Vec<Instruction>);
+
+fn load_data(data: &str, wide: bool) -> Result<Setup> {
+    let warehouse: Warehouse = data.parse()?;
+    let map = if wide { widen(warehouse.map) } else { warehouse.map };
+    let robot_loc = map.iter().enumerate()
+        .find_map(|(y, row)| row.iter().position(|&item| item == Robot).map(|x| (x, y)))
+        .context("No robot in map")?;
+    Ok((robot_loc, map, warehouse.moves.into_iter().flatten().collect()))
+}
+
+fn render_map(map: &[Vec<MapItem>]) -> String {
+    let mut out = String::new();
     for row in map {
         for item in row {
-            print!("{}", item);
+            out.push_str(&item.to_string());
         }
-        println!();
+        out.push('\n');
     }
+    out
 }
 
-fn follow_instructions(mut robot_x: usize, mut robot_y: usize, map: &mut Vec<Vec<MapItem>>, instructions: Vec<Instruction>) {
-    for instruction in instructions.iter() {
-        // println!("{}", instruction);
-        match instruction {
-            Up => if can_move_item(map, robot_x, robot_y, Up) {
-                move_item(map, robot_x, robot_y, Up);
-                robot_y -= 1;
-            }
-            Down => if can_move_item(map, robot_x, robot_y, Down) {
-                move_item(map, robot_x, robot_y, Down);
-                robot_y += 1;
-            }
-            Left => if can_move_item(map, robot_x, robot_y, Left) {
-                move_item(map, robot_x, robot_y, Left);
-                robot_x -= 1;
-            }
-            Right => if can_move_item(map, robot_x, robot_y, Right) {
-                move_item(map, robot_x, robot_y, Right);
-                robot_x += 1;
+struct Script {
+    instructions: Vec<Instruction>,
+}
+
+#[derive(Clone)]
+struct Floor {
+    robot_x: usize,
+    robot_y: usize,
+    map: Vec<Vec<MapItem>>,
+    next: usize,
+}
+
+impl Simulation for Script {
+    type State = Floor;
+
+    fn step(&self, floor: &mut Floor) {
+        let instruction = self.instructions[floor.next];
+        floor.next += 1;
+        if can_move_item(&floor.map, floor.robot_x, floor.robot_y, instruction) {
+            move_item(&mut floor.map, floor.robot_x, floor.robot_y, instruction);
+            match instruction {
+                Up => floor.robot_y -= 1,
+                Down => floor.robot_y += 1,
+                Left => floor.robot_x -= 1,
+                Right => floor.robot_x += 1,
             }
         }
-        // print_map(map);
     }
+
+    fn is_done(&self, floor: &Floor) -> bool {
+        floor.next == self.instructions.len()
+    }
+}
+
+fn follow_instructions(data: &str, wide: bool) -> Result<Vec<Vec<MapItem>>> {
+    let ((robot_x, robot_y), map, instructions) = load_data(data, wide)?;
+    let mut runner = Runner::new(Script { instructions }, Floor { robot_x, robot_y, map, next: 0 });
+    runner.finish();
+    Ok(runner.into_state().map)
 }
 
-fn can_move_item(map: &Vec<Vec<MapItem>>, x: usize, y: usize, direction: Instruction) -> bool {
+fn can_move_item(map: &[Vec<MapItem>], x: usize, y: usize, direction: Instruction) -> bool {
     let (next_x, next_y) = match direction {
         Up => (x, y - 1),
         Down => (x, y + 1),
@@ -136,19 +179,11 @@ fn can_move_item(map: &Vec<Vec<MapItem>>, x: usize, y: usize, direction: Instruc
     if map[next_y][next_x] == Nothing {
         true
     } else if map[next_y][next_x] == Carton {
-        if can_move_item(map, next_x, next_y, direction) {
-            true
-        } else {
-            false
-        }
+        can_move_item(map, next_x, next_y, direction)
     } else if map[next_y][next_x] == CartonL {
         if can_move_item(map, next_x, next_y, direction) {
             if direction == Up || direction == Down {
-                if can_move_item(map, next_x + 1, next_y, direction) {
-                    true
-                } else {
-                    false
-                }
+                can_move_item(map, next_x + 1, next_y, direction)
             } else {
                 true
             }
@@ -158,11 +193,7 @@ fn can_move_item(map: &Vec<Vec<MapItem>>, x: usize, y: usize, direction: Instruc
     } else if map[next_y][next_x] == CartonR {
         if can_move_item(map, next_x, next_y, direction) {
             if direction == Up || direction == Down {
-                if can_move_item(map, next_x - 1, next_y, direction) {
-                    true
-                } else {
-                    false
-                }
+                can_move_item(map, next_x - 1, next_y, direction)
             } else {
                 true
             }
@@ -200,72 +231,75 @@ fn move_item(map: &mut Vec<Vec<MapItem>>, x: usize, y: usize, direction: Instruc
     }
 }
 
-pub fn sum_of_gps_coordinates(data: &str, wide: bool) -> usize {
-    let ((robot_x, robot_y), mut map, instructions) = load_data(data, wide);
-    // print_map(&map);
-    follow_instructions(robot_x, robot_y, &mut map, instructions);
-    map.iter().enumerate().map(|(y, row)| { 
+pub fn sum_of_gps_coordinates(data: &str, wide: bool) -> Result<usize> {
+    let map = follow_instructions(data, wide)?;
+    Ok(map.iter().enumerate().map(|(y, row)| { 
         row.iter().enumerate()
             .filter(|(_, &item)| item == Carton || item == CartonL)
             .map(|(x, _)| y * 100 + x)
             .sum::<usize>() 
-    }).sum()
+    }).sum())
+}
+
+pub fn render_warehouse(data: &str, wide: bool) -> Result<String> {
+    Ok(render_map(&follow_instructions(data, wide)?))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::snapshot::assert_snapshot;
 
-    const SMALL_EXAMPLE: &str = r"########
-#..O.O.#
-##@.O..#
-#...O..#
-#.#.O..#
-#...O..#
-#......#
-########
+    const SMALL_EXAMPLE: &str = include_str!("../res/examples/day15_small.txt");
+    const BIG_EXAMPLE: &str = include_str!("../res/examples/day15_big.txt");
 
-<^^>>>vv<v>>v<<";
-    const BIG_EXAMPLE: &str = r"##########
-#..O..O.O#
-#......O.#
-#.OO..O.O#
-#..O@..O.#
-#O#..O...#
-#O..O..O.#
-#.OO.O.OO#
-#....O...#
-##########
+    #[test]
+    fn example_1() -> Result<()> {
+        assert_eq!(sum_of_gps_coordinates(SMALL_EXAMPLE, false)?, 2028);
+        assert_eq!(sum_of_gps_coordinates(BIG_EXAMPLE, false)?, 10092);
+        Ok(())
+    }
 
-<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
-vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
-><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
-<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
-^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
-^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
->^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
-<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
-^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
-v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
+    #[test]
+    fn example_2() -> Result<()> {
+        assert_eq!(sum_of_gps_coordinates(BIG_EXAMPLE, true)?, 9021);
+        Ok(())
+    }
+
+    #[test]
+    fn model_round_trips() -> Result<()> {
+        let warehouse: Warehouse = SMALL_EXAMPLE.parse()?;
+        assert_eq!(warehouse.map[2][2], Robot);
+        assert_eq!(warehouse.moves[0][..3], [Left, Up, Up]);
+        assert_eq!(warehouse.to_string(), SMALL_EXAMPLE);
+        assert_eq!(BIG_EXAMPLE.parse::<Warehouse>()?.to_string(), BIG_EXAMPLE);
+        assert_eq!("#.#\n\n<".parse::<Warehouse>().unwrap_err().to_string(), "Expected exactly one robot in map");
+        Ok(())
+    }
 
     #[test]
-    fn example_1() {
-        assert_eq!(sum_of_gps_coordinates(SMALL_EXAMPLE, false), 2028);
-        assert_eq!(sum_of_gps_coordinates(BIG_EXAMPLE, false), 10092);
+    fn render_example() -> Result<()> {
+        assert_eq!(render_warehouse(SMALL_EXAMPLE, false)?, "########\n#....OO#\n##.....#\n#.....O#\n#.#O@..#\n#...O..#\n#...O..#\n########\n");
+        Ok(())
     }
 
     #[test]
-    fn example_2() {
-        assert_eq!(sum_of_gps_coordinates(BIG_EXAMPLE, true), 9021);
+    fn warehouse_snapshots() -> Result<()> {
+        assert_snapshot("day15_big_example", &render_warehouse(BIG_EXAMPLE, false)?);
+        assert_snapshot("day15_big_example_wide", &render_warehouse(BIG_EXAMPLE, true)?);
+        assert_snapshot("day15_input_wide", &render_warehouse(include_str!("../res/day15.txt"), true)?);
+        Ok(())
     }
 
     #[test]
-    fn part_1() {
-        println!("Part 1: {}", sum_of_gps_coordinates(include_str!("../res/day15.txt"), false));
+    fn part_1() -> Result<()> {
+        println!("Part 1: {}", sum_of_gps_coordinates(include_str!("../res/day15.txt"), false)?);
+        Ok(())
     }
 
     #[test]
-    fn part_2() {
-        println!("Part 2: {}", sum_of_gps_coordinates(include_str!("../res/day15.txt"), true));
+    fn part_2() -> Result<()> {
+        println!("Part 2: {}", sum_of_gps_coordinates(include_str!("../res/day15.txt"), true)?);
+        Ok(())
     }
 }
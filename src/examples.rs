@@ -0,0 +1,166 @@
+use crate::cancel::Cancel;
+use crate::config;
+use crate::params::Params;
+use crate::progress::NoProgress;
+use crate::solvers;
+use anyhow::{bail, Context, Result};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DIR: &str = "res/examples";
+const MANIFEST: &str = "manifest.toml";
+
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub day: u8,
+    pub file: String,
+    pub params: Vec<(String, String)>,
+    pub expected: Vec<(u8, String)>,
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub part: u8,
+    pub expected: String,
+    pub actual: Result<String>,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.actual.as_ref().is_ok_and(|actual| *actual == self.expected)
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.actual {
+            Ok(actual) if *actual == self.expected => write!(f, "part {}: {} ok", self.part, actual),
+            Ok(actual) => write!(f, "part {}: {} FAILED, expected {}", self.part, actual, self.expected),
+            Err(e) => write!(f, "part {}: ERROR {:#}", self.part, e),
+        }
+    }
+}
+
+pub fn parse_manifest(text: &str) -> Result<Vec<Example>> {
+    let mut examples = vec![];
+    for section in config::parse(text)?.into_iter().filter(|section| !section.name.is_empty()) {
+        let day = section.get("day").with_context(|| format!("Example {} has no day", section.name))?;
+        let day = day.parse().with_context(|| format!("Couldn't parse day of example {}: {}", section.name, day))?;
+        let mut example = Example {
+            file: section.get("file").map(str::to_string).unwrap_or_else(|| format!("{}.txt", section.name)),
+            name: section.name.clone(),
+            day,
+            params: vec![],
+            expected: vec![],
+        };
+        for (key, value) in &section.entries {
+            match key.as_str() {
+                "day" | "file" => {}
+                "part1" => example.expected.push((1, value.clone())),
+                "part2" => example.expected.push((2, value.clone())),
+                key if key.contains('.') => example.params.push((key.to_string(), value.clone())),
+                key => bail!("Unknown key {} in example {}", key, section.name),
+            }
+        }
+        examples.push(example);
+    }
+    Ok(examples)
+}
+
+pub fn load_manifest<P: AsRef<Path>>(dir: P) -> Result<Vec<Example>> {
+    let path = dir.as_ref().join(MANIFEST);
+    let text = fs::read_to_string(&path).with_context(|| format!("Couldn't read {}", path.display()))?;
+    parse_manifest(&text).with_context(|| format!("Couldn't parse {}", path.display()))
+}
+
+impl Example {
+    pub fn path<P: AsRef<Path>>(&self, dir: P) -> PathBuf {
+        dir.as_ref().join(&self.file)
+    }
+
+    pub fn params(&self, base: &Params) -> Result<Params> {
+        let mut params = base.clone();
+        for (key, value) in &self.params {
+            params.set(key, value).with_context(|| format!("In example {}", self.name))?;
+        }
+        Ok(params)
+    }
+
+    /// Solves the example's expected parts, or just `part` when given.
+    pub fn check<P: AsRef<Path>>(&self, dir: P, base: &Params, part: Option<u8>, cancel: &Cancel) -> Result<Vec<Check>> {
+        let path = self.path(dir);
+        let data = fs::read_to_string(&path).with_context(|| format!("Couldn't read {}", path.display()))?;
+        let params = self.params(base)?;
+        let mut checks = vec![];
+        for (part, expected) in self.expected.iter().filter(|(other, _)| part.is_none_or(|part| *other == part)) {
+            let solver = solvers::find(self.day, *part).with_context(|| format!("No solver for day {} part {}", self.day, part))?;
+            let actual = (solver.solve)(data.trim_end(), &params, cancel, &NoProgress);
+            checks.push(Check { part: *part, expected: expected.clone(), actual });
+        }
+        Ok(checks)
+    }
+}
+
+pub fn self_check<P: AsRef<Path>>(examples: &[Example], dir: P, day: u8, part: u8, base: &Params, cancel: &Cancel) -> Result<Vec<String>> {
+    let mut failures = vec![];
+    for example in examples.iter().filter(|example| example.day == day) {
+        for check in example.check(&dir, base, Some(part), cancel)?.into_iter().filter(|check| !check.passed()) {
+            failures.push(format!("{} {}", example.name, check));
+        }
+    }
+    Ok(failures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_manifest_entries() -> Result<()> {
+        let examples = parse_manifest("[day14]\nday = 14\nday14.map_x = 11\npart1 = 12\n\n[other]\nday = 3\nfile = shared.txt\npart2 = 48\n")?;
+        assert_eq!(examples, vec![
+            Example { name: "day14".to_string(), day: 14, file: "day14.txt".to_string(), params: vec![("day14.map_x".to_string(), "11".to_string())], expected: vec![(1, "12".to_string())] },
+            Example { name: "other".to_string(), day: 3, file: "shared.txt".to_string(), params: vec![], expected: vec![(2, "48".to_string())] },
+        ]);
+        assert_eq!(parse_manifest("[x]\nday = 1\nanswer = 2\n").unwrap_err().to_string(), "Unknown key answer in example x");
+        Ok(())
+    }
+
+    #[test]
+    fn every_example_passes() -> Result<()> {
+        for example in load_manifest(DIR)? {
+            for check in example.check(DIR, &Params::default(), None, &Cancel::never())? {
+                assert!(check.passed(), "{}: {}", example.name, check);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn self_check_reports_mismatches() -> Result<()> {
+        assert!(self_check(&load_manifest(DIR)?, DIR, 1, 2, &Params::default(), &Cancel::never())?.is_empty());
+        let broken = Example { name: "broken".to_string(), day: 1, file: "day01.txt".to_string(), params: vec![], expected: vec![(1, "12".to_string()), (2, "31".to_string())] };
+        assert_eq!(self_check(&[broken], DIR, 1, 1, &Params::default(), &Cancel::never())?, vec!["broken part 1: 11 FAILED, expected 12"]);
+        Ok(())
+    }
+
+    #[test]
+    fn checks_only_the_requested_part() -> Result<()> {
+        let example = Example { name: "day01".to_string(), day: 1, file: "day01.txt".to_string(), params: vec![], expected: vec![(1, "11".to_string()), (2, "31".to_string())] };
+        let checks = example.check(DIR, &Params::default(), Some(2), &Cancel::never())?;
+        assert_eq!(checks.iter().map(|check| check.part).collect::<Vec<_>>(), vec![2]);
+        Ok(())
+    }
+
+    #[test]
+    fn self_check_honours_the_cancel_token() -> Result<()> {
+        let cancel = Cancel::never();
+        cancel.cancel();
+        let failures = self_check(&load_manifest(DIR)?, DIR, 6, 2, &Params::default(), &cancel)?;
+        assert!(failures.iter().all(|failure| failure.ends_with("ERROR Solver was cancelled")), "{:?}", failures);
+        assert!(!failures.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+#[derive(Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Explanation {
+    pub answer: String,
+    pub records: Vec<Record>,
+}
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Record {
+    pub label: String,
+    pub decision: String,
+    pub values: Vec<(String, String)>,
+}
+
+impl Explanation {
+    pub fn record<L: ToString, D: ToString>(&mut self, label: L, decision: D, values: Vec<(&str, String)>) {
+        self.records.push(Record {
+            label: label.to_string(),
+            decision: decision.to_string(),
+            values: values.into_iter().map(|(name, value)| (name.to_string(), value)).collect(),
+        });
+    }
+
+    pub fn count(&self, decision: &str) -> usize {
+        self.records.iter().filter(|record| record.decision == decision).count()
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} => {}", self.label, self.decision)?;
+        if !self.values.is_empty() {
+            let values: Vec<String> = self.values.iter().map(|(name, value)| format!("{}: {}", name, value)).collect();
+            write!(f, " [{}]", values.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for record in self.records.iter() {
+            writeln!(f, "{}", record)?;
+        }
+        writeln!(f, "Answer: {}", self.answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn displays_records_then_answer() {
+        let mut explanation = Explanation::default();
+        explanation.record("1 2 3", "safe", vec![("differences", "1 1".to_string())]);
+        explanation.record("1 9", "unsafe", vec![]);
+        explanation.answer = explanation.count("safe").to_string();
+        assert_eq!(explanation.to_string(), "1 2 3 => safe [differences: 1 1]\n1 9 => unsafe\nAnswer: 1\n");
+    }
+}
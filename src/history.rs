@@ -0,0 +1,108 @@
+use crate::config;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+pub const DEFAULT_PATH: &str = ".advent2024/history.toml";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl History {
+    pub fn parse(text: &str) -> Result<History> {
+        let mut runs = vec![];
+        for section in config::parse(text)?.into_iter().filter(|section| !section.name.is_empty()) {
+            let day = section.name.strip_prefix("day").and_then(|day| day.parse().ok())
+                .with_context(|| format!("Unexpected history section: {}", section.name))?;
+            for part in 1..=2 {
+                if let Some(answer) = section.get(&format!("part{}", part)) {
+                    let micros = section.get(&format!("part{}_micros", part)).unwrap_or("0");
+                    let micros = micros.parse().with_context(|| format!("Couldn't parse day {} part {} runtime: {}", day, part, micros))?;
+                    runs.push(Run { day, part, answer: answer.to_string(), elapsed: Duration::from_micros(micros) });
+                }
+            }
+        }
+        Ok(History { runs })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<History> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(History::default());
+        }
+        let text = fs::read_to_string(path).with_context(|| format!("Couldn't read {}", path.display()))?;
+        History::parse(&text).with_context(|| format!("Couldn't parse {}", path.display()))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("Couldn't create {}", parent.display()))?;
+        }
+        fs::write(path, self.to_text()).with_context(|| format!("Couldn't write {}", path.display()))
+    }
+
+    pub fn record(&mut self, run: Run) {
+        self.runs.retain(|other| (other.day, other.part) != (run.day, run.part));
+        self.runs.push(run);
+        self.runs.sort_by_key(|run| (run.day, run.part));
+    }
+
+    pub fn last(&self, day: u8, part: u8) -> Option<&Run> {
+        self.runs.iter().find(|run| run.day == day && run.part == part)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let mut day = None;
+        for run in &self.runs {
+            if day != Some(run.day) {
+                if day.is_some() {
+                    text.push('\n');
+                }
+                text.push_str(&format!("[day{:02}]\n", run.day));
+                day = Some(run.day);
+            }
+            text.push_str(&format!("part{} = \"{}\"\npart{}_micros = {}\n", run.part, run.answer, run.part, run.elapsed.as_micros()));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(day: u8, part: u8, answer: &str, micros: u64) -> Run {
+        Run { day, part, answer: answer.to_string(), elapsed: Duration::from_micros(micros) }
+    }
+
+    #[test]
+    fn records_latest_runs() -> Result<()> {
+        let mut history = History::default();
+        history.record(run(6, 2, "1", 100));
+        history.record(run(1, 1, "11", 5));
+        history.record(run(6, 2, "1748", 2000));
+        assert_eq!(history.runs, vec![run(1, 1, "11", 5), run(6, 2, "1748", 2000)]);
+        assert_eq!(history.last(6, 2), Some(&run(6, 2, "1748", 2000)));
+        assert_eq!(history.last(6, 1), None);
+        assert_eq!(History::parse(&history.to_text())?, history);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_sections() {
+        assert_eq!(History::parse("[nonsense]\n").unwrap_err().to_string(), "Unexpected history section: nonsense");
+    }
+}
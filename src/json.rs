@@ -0,0 +1,255 @@
+use anyhow::{bail, Context, Result};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_object()?.iter().find(|(name, _)| name == key).map(|(_, value)| value)
+    }
+
+    pub fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_f64().filter(|value| *value >= 0.0 && value.fract() == 0.0).map(|value| value as u64)
+    }
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.text.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.text.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        match self.peek() {
+            Some(found) if found == byte => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(found) => bail!("Expected '{}' at offset {}, found '{}'", byte as char, self.pos, found as char),
+            None => bail!("Expected '{}' at offset {}, found end of input", byte as char, self.pos),
+        }
+    }
+
+    fn keyword(&mut self, word: &str, value: Value) -> Result<Value> {
+        if !self.text[self.pos..].starts_with(word.as_bytes()) {
+            bail!("Unexpected token at offset {}", self.pos);
+        }
+        self.pos += word.len();
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        match self.peek() {
+            None => bail!("Unexpected end of input"),
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => Ok(Value::String(self.string()?)),
+            Some(b't') => self.keyword("true", Value::Bool(true)),
+            Some(b'f') => self.keyword("false", Value::Bool(false)),
+            Some(b'n') => self.keyword("null", Value::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(found) => bail!("Unexpected '{}' at offset {}", found as char, self.pos),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value> {
+        self.expect(b'{')?;
+        let mut entries = vec![];
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Value::Object(entries));
+        }
+        loop {
+            if self.peek() != Some(b'"') {
+                bail!("Expected object key at offset {}", self.pos);
+            }
+            let key = self.string()?;
+            self.expect(b':')?;
+            entries.push((key, self.value()?));
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                _ => break,
+            }
+        }
+        self.expect(b'}')?;
+        Ok(Value::Object(entries))
+    }
+
+    fn array(&mut self) -> Result<Value> {
+        self.expect(b'[')?;
+        let mut values = vec![];
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Value::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                _ => break,
+            }
+        }
+        self.expect(b']')?;
+        Ok(Value::Array(values))
+    }
+
+    fn hex4(&mut self) -> Result<u32> {
+        let digits = self.text.get(self.pos..self.pos + 4).with_context(|| format!("Truncated unicode escape at offset {}", self.pos))?;
+        let digits = std::str::from_utf8(digits)?;
+        let code = u32::from_str_radix(digits, 16).with_context(|| format!("Bad unicode escape at offset {}: {}", self.pos, digits))?;
+        self.pos += 4;
+        Ok(code)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.expect(b'"')?;
+        let mut bytes = vec![];
+        loop {
+            let byte = *self.text.get(self.pos).context("Unterminated string")?;
+            self.pos += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escape = *self.text.get(self.pos).context("Unterminated string")?;
+                    self.pos += 1;
+                    let decoded = match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let mut code = self.hex4()?;
+                            if (0xd800..0xdc00).contains(&code) && self.text[self.pos..].starts_with(b"\\u") {
+                                self.pos += 2;
+                                let low = self.hex4()?;
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low.wrapping_sub(0xdc00) & 0x3ff);
+                            }
+                            char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                        }
+                        _ => bail!("Unknown escape '\\{}' at offset {}", escape as char, self.pos - 1),
+                    };
+                    bytes.extend_from_slice(decoded.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                _ => bytes.push(byte),
+            }
+        }
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    fn number(&mut self) -> Result<Value> {
+        let start = self.pos;
+        while self.text.get(self.pos).is_some_and(|byte| matches!(byte, b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        let literal = std::str::from_utf8(&self.text[start..self.pos])?;
+        Ok(Value::Number(literal.parse().with_context(|| format!("Bad number at offset {}: {}", start, literal))?))
+    }
+}
+
+pub fn parse(text: &str) -> Result<Value> {
+    let mut parser = Parser { text: text.as_bytes(), pos: 0 };
+    let value = parser.value()?;
+    if parser.peek().is_some() {
+        bail!("Trailing characters at offset {}", parser.pos);
+    }
+    Ok(value)
+}
+
+/// Writes `value` as a quoted JSON string.
+pub fn quote(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_values() -> Result<()> {
+        let value = parse(r#" {"a": [1, -2.5e1, true, null], "b": {"c": "x\"y\u00e9\ud83c\udf84"}, "d": []} "#)?;
+        assert_eq!(value.get("a"), Some(&Value::Array(vec![Value::Number(1.0), Value::Number(-25.0), Value::Bool(true), Value::Null])));
+        assert_eq!(value.get("b").and_then(|b| b.get("c")).and_then(Value::as_str), Some("x\"yé🎄"));
+        assert_eq!(value.get("d").and_then(Value::as_array), Some(&[][..]));
+        assert_eq!(value.get("a").and_then(Value::as_array).and_then(|a| a[0].as_u64()), Some(1));
+        Ok(())
+    }
+
+    #[test]
+    fn quotes_strings() -> Result<()> {
+        assert_eq!(quote("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+        assert_eq!(parse(&quote("x\"y\té\u{1}"))?.as_str(), Some("x\"y\té\u{1}"));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert_eq!(parse("{\"a\" 1}").unwrap_err().to_string(), "Expected ':' at offset 5, found '1'");
+        assert_eq!(parse("[1, 2").unwrap_err().to_string(), "Expected ']' at offset 5, found end of input");
+        assert_eq!(parse("\"open").unwrap_err().to_string(), "Unterminated string");
+        assert_eq!(parse("1 2").unwrap_err().to_string(), "Trailing characters at offset 2");
+    }
+}
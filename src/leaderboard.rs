@@ -0,0 +1,230 @@
+use crate::json::{self, Value};
+use anyhow::{bail, Context, Result};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+const DAY_SECONDS: u64 = 24 * 60 * 60;
+const RELEASE_HOUR_UTC: u64 = 5;
+const DAYS: std::ops::RangeInclusive<u8> = 1..=25;
+const PARTS: std::ops::RangeInclusive<u8> = 1..=2;
+const EVENTS: std::ops::RangeInclusive<i64> = 2015..=9999;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Star {
+    pub day: u8,
+    pub part: u8,
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Member {
+    pub id: u64,
+    pub name: String,
+    pub stars: Vec<Star>,
+}
+
+impl Member {
+    pub fn star(&self, day: u8, part: u8) -> Option<&Star> {
+        self.stars.iter().find(|star| star.day == day && star.part == part)
+    }
+
+    fn last_star(&self) -> u64 {
+        self.stars.iter().map(|star| star.timestamp).max().unwrap_or(u64::MAX)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Standing<'a> {
+    pub member: &'a Member,
+    pub stars: usize,
+    pub local_score: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Leaderboard {
+    pub event: i64,
+    pub day1_release: u64,
+    pub members: Vec<Member>,
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn parse_member(key: &str, value: &Value) -> Result<Member> {
+    let id = value.get("id").and_then(Value::as_u64).or_else(|| key.parse().ok()).with_context(|| format!("Member {} has no id", key))?;
+    let name = match value.get("name").and_then(Value::as_str) {
+        Some(name) => name.to_string(),
+        None => format!("(anonymous user #{})", id),
+    };
+    let mut stars = vec![];
+    for (day, parts) in value.get("completion_day_level").and_then(Value::as_object).unwrap_or_default() {
+        let day: u8 = day.parse().with_context(|| format!("Couldn't parse day {} for member {}", day, id))?;
+        if !DAYS.contains(&day) {
+            bail!("Day {} of member {} is outside the event", day, id);
+        }
+        for (part, completion) in parts.as_object().with_context(|| format!("Day {} of member {} isn't an object", day, id))? {
+            let part: u8 = part.parse().with_context(|| format!("Couldn't parse part {} of day {} for member {}", part, day, id))?;
+            if !PARTS.contains(&part) {
+                bail!("Part {} of day {} for member {} is outside the event", part, day, id);
+            }
+            let timestamp = completion.get("get_star_ts").and_then(Value::as_u64)
+                .with_context(|| format!("Day {} part {} of member {} has no get_star_ts", day, part, id))?;
+            stars.push(Star { day, part, timestamp });
+        }
+    }
+    stars.sort_by_key(|star| (star.day, star.part));
+    Ok(Member { id, name, stars })
+}
+
+impl Leaderboard {
+    pub fn parse(text: &str) -> Result<Leaderboard> {
+        let root = json::parse(text)?;
+        let event = match root.get("event") {
+            Some(Value::String(event)) => event.parse().with_context(|| format!("Couldn't parse event: {}", event))?,
+            Some(Value::Number(event)) => *event as i64,
+            _ => bail!("Leaderboard has no event"),
+        };
+        if !EVENTS.contains(&event) {
+            bail!("Event {} is outside the years Advent of Code can run", event);
+        }
+        let day1_release = match root.get("day1_ts").and_then(Value::as_u64) {
+            Some(timestamp) => timestamp,
+            None => days_from_civil(event, 12, 1) as u64 * DAY_SECONDS + RELEASE_HOUR_UTC * 60 * 60,
+        };
+        let members = root.get("members").and_then(Value::as_object).context("Leaderboard has no members")?;
+        let mut members = members.iter().map(|(key, value)| parse_member(key, value)).collect::<Result<Vec<_>>>()?;
+        members.sort_by_key(|member| member.id);
+        Ok(Leaderboard { event, day1_release, members })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Leaderboard> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).with_context(|| format!("Couldn't read {}", path.display()))?;
+        Leaderboard::parse(&text).with_context(|| format!("Couldn't parse {}", path.display()))
+    }
+
+    pub fn release(&self, day: u8) -> Option<u64> {
+        DAYS.contains(&day).then(|| self.day1_release + (day as u64 - 1) * DAY_SECONDS)
+    }
+
+    pub fn completion_time(&self, member: &Member, day: u8, part: u8) -> Option<u64> {
+        let release = self.release(day)?;
+        member.star(day, part).map(|star| star.timestamp.saturating_sub(release))
+    }
+
+    pub fn part2_delta(&self, member: &Member, day: u8) -> Option<u64> {
+        Some(member.star(day, 2)?.timestamp.saturating_sub(member.star(day, 1)?.timestamp))
+    }
+
+    pub fn days(&self) -> Vec<u8> {
+        let mut days: Vec<u8> = self.members.iter().flat_map(|member| member.stars.iter().map(|star| star.day)).collect();
+        days.sort();
+        days.dedup();
+        days
+    }
+
+    pub fn local_score(&self, member: &Member) -> usize {
+        member.stars.iter().map(|star| {
+            let earlier = self.members.iter()
+                .filter(|other| other.star(star.day, star.part).is_some_and(|other| other.timestamp < star.timestamp))
+                .count();
+            self.members.len() - earlier
+        }).sum()
+    }
+
+    pub fn standings(&self) -> Vec<Standing<'_>> {
+        let mut standings: Vec<Standing> = self.members.iter()
+            .map(|member| Standing { member, stars: member.stars.len(), local_score: self.local_score(member) })
+            .collect();
+        standings.sort_by(|a, b| b.local_score.cmp(&a.local_score).then(a.member.last_star().cmp(&b.member.last_star())));
+        standings
+    }
+}
+
+fn format_duration(seconds: Option<u64>) -> String {
+    match seconds {
+        None => "-".to_string(),
+        Some(seconds) => format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60),
+    }
+}
+
+impl Display for Leaderboard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Advent of Code {} private leaderboard, {} members", self.event, self.members.len())?;
+        writeln!(f)?;
+        writeln!(f, "Rank  Member                      Stars  Score")?;
+        for (rank, standing) in self.standings().iter().enumerate() {
+            writeln!(f, "{:>4}  {:<26}  {:>5}  {:>5}", rank + 1, standing.member.name, standing.stars, standing.local_score)?;
+        }
+        for day in self.days() {
+            writeln!(f)?;
+            writeln!(f, "Day {:<2}  Member                          Part 1      Part 2       Delta", day)?;
+            let mut finishers: Vec<&Member> = self.members.iter().filter(|member| member.star(day, 1).is_some()).collect();
+            finishers.sort_by_key(|member| (self.completion_time(member, day, 2).unwrap_or(u64::MAX), self.completion_time(member, day, 1)));
+            for member in finishers {
+                writeln!(f, "        {:<26}  {:>10}  {:>10}  {:>10}", member.name,
+                         format_duration(self.completion_time(member, day, 1)),
+                         format_duration(self.completion_time(member, day, 2)),
+                         format_duration(self.part2_delta(member, day)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../res/leaderboard/example.json");
+
+    #[test]
+    fn computes_release_times() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2024, 12, 1), 20058);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+    }
+
+    #[test]
+    fn computes_member_statistics() -> Result<()> {
+        let leaderboard = Leaderboard::parse(EXAMPLE)?;
+        assert_eq!(leaderboard.release(2), Some(1733115600));
+        assert_eq!(leaderboard.release(0), None);
+        assert_eq!(leaderboard.release(26), None);
+        let standings: Vec<(&str, usize, usize)> = leaderboard.standings().iter()
+            .map(|standing| (standing.member.name.as_str(), standing.stars, standing.local_score))
+            .collect();
+        assert_eq!(standings, vec![("Alice", 4, 11), ("Bob", 3, 7), ("(anonymous user #303)", 1, 1)]);
+        let alice = &leaderboard.members[0];
+        assert_eq!(leaderboard.completion_time(alice, 1, 1), Some(312));
+        assert_eq!(leaderboard.part2_delta(alice, 1), Some(208));
+        assert_eq!(leaderboard.part2_delta(&leaderboard.members[2], 1), None);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_fixture_table() -> Result<()> {
+        assert_eq!(Leaderboard::parse(EXAMPLE)?.to_string(), include_str!("../res/leaderboard/example.txt"));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_missing_members() {
+        assert_eq!(Leaderboard::parse(r#"{"event": "2024"}"#).unwrap_err().to_string(), "Leaderboard has no members");
+        let day_zero = r#"{"event": "2024", "members": {"1": {"completion_day_level": {"0": {"1": {"get_star_ts": 1}}}}}}"#;
+        assert_eq!(Leaderboard::parse(day_zero).unwrap_err().to_string(), "Day 0 of member 1 is outside the event");
+        for part in ["0", "3"] {
+            let bad_part = format!(r#"{{"event": "2024", "members": {{"1": {{"completion_day_level": {{"2": {{"{}": {{"get_star_ts": 1}}}}}}}}}}}}"#, part);
+            assert_eq!(Leaderboard::parse(&bad_part).unwrap_err().to_string(), format!("Part {} of day 2 for member 1 is outside the event", part));
+        }
+        assert_eq!(Leaderboard::parse(r#"{"event": "1969", "members": {}}"#).unwrap_err().to_string(), "Event 1969 is outside the years Advent of Code can run");
+        assert_eq!(Leaderboard::parse(r#"{"event": 1e300, "members": {}}"#).unwrap_err().to_string(), format!("Event {} is outside the years Advent of Code can run", i64::MAX));
+    }
+}
@@ -1,16 +1,76 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod answers;
+#[cfg(feature = "std")]
+pub mod batch;
+pub mod bigint;
+pub mod bitgrid;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod calendar;
+#[cfg(feature = "std")]
+pub mod cancel;
+#[cfg(feature = "std")]
+pub mod catalogue;
+#[cfg(feature = "std")]
+pub mod config;
+pub mod cycle;
 pub mod day01;
+#[cfg(feature = "std")]
 pub mod day02;
+#[cfg(feature = "std")]
 pub mod day03;
+#[cfg(feature = "std")]
 pub mod day04;
+#[cfg(feature = "std")]
 pub mod day05;
+#[cfg(feature = "std")]
 pub mod day06;
+#[cfg(feature = "std")]
 pub mod day07;
+#[cfg(feature = "std")]
 pub mod day08;
 pub mod day09;
+#[cfg(feature = "std")]
 pub mod day10;
 pub mod day11;
+#[cfg(feature = "std")]
 pub mod day12;
 pub mod day13;
+#[cfg(feature = "std")]
 pub mod day14;
+#[cfg(feature = "std")]
 pub mod day15;
+#[cfg(feature = "std")]
+pub mod examples;
+pub mod explain;
+#[cfg(feature = "std")]
+pub mod history;
+#[cfg(feature = "std")]
+pub mod json;
+#[cfg(feature = "std")]
+pub mod leaderboard;
+#[cfg(feature = "std")]
+pub mod memory;
+#[cfg(feature = "std")]
+pub mod params;
+#[cfg(feature = "std")]
+pub mod progress;
+#[cfg(feature = "std")]
+pub mod repl;
+pub mod scan;
+#[cfg(feature = "std")]
+pub mod server;
+pub mod simulation;
+#[cfg(feature = "std")]
+pub mod solvers;
+#[cfg(feature = "std")]
+pub mod watch;
+#[cfg(all(test, feature = "std"))]
+mod snapshot;
+#[cfg(feature = "std")]
 mod template;
@@ -0,0 +1,332 @@
+use advent2024::answers::{self, Answers};
+use advent2024::batch;
+use advent2024::cache::{self, Cache, Entry};
+use advent2024::calendar;
+use advent2024::cancel::Cancel;
+use advent2024::catalogue;
+use advent2024::examples;
+use advent2024::history::{self, History, Run};
+use advent2024::leaderboard::Leaderboard;
+use advent2024::memory;
+use advent2024::params::Params;
+use advent2024::progress::{NoProgress, ProgressBar};
+use advent2024::repl::Repl;
+use advent2024::server::Server;
+use advent2024::solvers::{self, Solver};
+use advent2024::watch;
+use anyhow::{bail, Context, Result};
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+const DEFAULT_CONFIG: &str = "advent2024.toml";
+const USAGE: &str = "Usage: advent2024 <command> [options]
+
+Commands:
+  run (--day <day> [--part <part>] [--input <path>] | --all) [--timeout <seconds>]
+      [--self-check] [--no-cache]                      Solve puzzles and print the answers with timings,
+                                                       optionally checking the day's examples pass first;
+                                                       answers are cached by input and solver version,
+                                                       and recorded in the history for res/dayNN.txt only
+  bench (--day <day> [--part <part>] | --all) [--iterations <count>]
+                                                       Time puzzles over repeated runs
+  batch --day <day> --dir <path> [--timeout <seconds>] Solve every input in a directory, checking <file>.expected answers
+  calendar                                             Show which days are solved and verified, with runtime badges
+  check-examples [--day <day>] [--dir <path>]          Solve the puzzle examples and compare with their expected answers
+  explain --day <day> --part <part> [--input <path>] [--output <path>]
+                                                       Show how an answer was derived, record by record
+  leaderboard --file <path>                            Summarise an exported private leaderboard JSON file
+  list [--day <day>]                                   Show every puzzle with its answers and last runtimes
+  serve [--addr <host:port>] [--timeout <seconds>]     Solve puzzles posted to /2024/day/<day>/part/<part>
+  repl [--day <day>]                                   Explore puzzle inputs interactively
+  watch --day <day> [--interval <millis>]              Rerun a day's tests whenever its input or source changes
+
+Options for every command:
+  --config <path>                                      Read puzzle parameters from a config file, advent2024.toml by default
+  --set <day>.<key>=<value>                            Override a puzzle parameter, may be repeated";
+
+fn option<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter().position(|arg| arg == name).and_then(|i| args.get(i + 1)).map(String::as_str)
+}
+
+fn options<'a>(args: &'a [String], name: &str) -> Vec<&'a str> {
+    args.windows(2).filter(|pair| pair[0] == name).map(|pair| pair[1].as_str()).collect()
+}
+
+fn parsed_option<T: FromStr>(args: &[String], name: &str) -> Result<Option<T>>
+where
+    T::Err: Display,
+{
+    match option(args, name) {
+        None => Ok(None),
+        Some(value) => match value.parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(e) => bail!("Couldn't parse {} {}: {}", name, value, e),
+        },
+    }
+}
+
+fn required_option<T: FromStr>(args: &[String], name: &str) -> Result<T>
+where
+    T::Err: Display,
+{
+    parsed_option(args, name)?.with_context(|| format!("Missing {}", name))
+}
+
+fn load_params(args: &[String]) -> Result<Params> {
+    let mut params = match option(args, "--config") {
+        Some(path) => Params::load(path)?,
+        None if Path::new(DEFAULT_CONFIG).exists() => Params::load(DEFAULT_CONFIG)?,
+        None => Params::default(),
+    };
+    for assignment in options(args, "--set") {
+        params.apply_override(assignment)?;
+    }
+    Ok(params)
+}
+
+fn read_input(day: u8, args: &[String]) -> Result<String> {
+    let path = option(args, "--input").map(str::to_string).unwrap_or_else(|| format!("res/day{:02}.txt", day));
+    Ok(fs::read_to_string(&path).with_context(|| format!("Couldn't read {}", path))?.trim_end().to_string())
+}
+
+fn select_solvers(args: &[String]) -> Result<Vec<(&'static Solver, String)>> {
+    let day: Option<u8> = parsed_option(args, "--day")?;
+    let part: Option<u8> = parsed_option(args, "--part")?;
+    if day.is_none() && !args.iter().any(|arg| arg == "--all") {
+        bail!("Expected --day <day> or --all");
+    }
+    let selected = solvers::SOLVERS.iter()
+        .filter(|solver| day.is_none_or(|day| solver.day == day))
+        .filter(|solver| part.is_none_or(|part| solver.part == part));
+    let mut inputs: Vec<(&'static Solver, String)> = vec![];
+    for solver in selected {
+        let data = match inputs.iter().find(|(other, _)| other.day == solver.day) {
+            Some((_, data)) => data.clone(),
+            None => read_input(solver.day, args)?,
+        };
+        inputs.push((solver, data));
+    }
+    Ok(inputs)
+}
+
+fn memory_usage(stats: memory::MemoryStats) -> String {
+    if memory::enabled() {
+        format!(", {}", stats)
+    } else {
+        String::new()
+    }
+}
+
+fn run(args: &[String]) -> Result<()> {
+    let params = load_params(args)?;
+    let timeout: Option<u64> = parsed_option(args, "--timeout")?;
+    let mut history = History::load(history::DEFAULT_PATH)?;
+    let examples = if args.iter().any(|arg| arg == "--self-check") { Some(examples::load_manifest(examples::DIR)?) } else { None };
+    let use_cache = !args.iter().any(|arg| arg == "--no-cache");
+    let mut cache = Cache::load(cache::DEFAULT_PATH)?;
+    let default_input = option(args, "--input").is_none();
+    let mut withheld = 0;
+    for (solver, data) in select_solvers(args)? {
+        let cancel = timeout.map(|seconds| Cancel::with_timeout(Duration::from_secs(seconds))).unwrap_or_default();
+        if let Some(examples) = &examples {
+            let failures = examples::self_check(examples, examples::DIR, solver.day, solver.part, &params, &cancel)?;
+            if !failures.is_empty() {
+                println!("Day {} part {}: examples failed, answer withheld", solver.day, solver.part);
+                for failure in failures {
+                    println!("  {}", failure);
+                }
+                withheld += 1;
+                continue;
+            }
+        }
+        let input = cache::input_hash(solver.day, &data, &params);
+        let version = format!("{:016x}", solver.version);
+        if use_cache {
+            if let Some(answer) = cache.get(solver.day, solver.part, &input, &version) {
+                println!("Day {} part {}: {} (cached)", solver.day, solver.part, answer);
+                continue;
+            }
+        }
+        let progress = ProgressBar::new(&format!("Day {} part {}", solver.day, solver.part));
+        let started = Instant::now();
+        let (result, stats) = memory::measure(|| (solver.solve)(&data, &params, &cancel, &progress));
+        let elapsed = started.elapsed();
+        progress.finish();
+        match result {
+            Ok(answer) => {
+                println!("Day {} part {}: {} ({:?}{})", solver.day, solver.part, answer, elapsed, memory_usage(stats));
+                cache.insert(Entry { day: solver.day, part: solver.part, input, version, answer: answer.clone() });
+                if default_input {
+                    history.record(Run { day: solver.day, part: solver.part, answer, elapsed });
+                }
+            }
+            Err(e) => println!("Day {} part {}: {:#} ({:?}{})", solver.day, solver.part, e, elapsed, memory_usage(stats)),
+        }
+    }
+    history.save(history::DEFAULT_PATH)?;
+    cache.save(cache::DEFAULT_PATH)?;
+    if withheld > 0 {
+        bail!("{} answers withheld after failed example checks", withheld);
+    }
+    Ok(())
+}
+
+fn bench(args: &[String]) -> Result<()> {
+    let params = load_params(args)?;
+    let iterations: u32 = parsed_option(args, "--iterations")?.unwrap_or(10);
+    if iterations == 0 {
+        bail!("Expected at least one iteration");
+    }
+    for (solver, data) in select_solvers(args)? {
+        let mut fastest = Duration::MAX;
+        let mut total = Duration::ZERO;
+        let mut stats = memory::MemoryStats::default();
+        for _ in 0..iterations {
+            let started = Instant::now();
+            let (result, measured) = memory::measure(|| (solver.solve)(&data, &params, &Cancel::never(), &NoProgress));
+            let elapsed = started.elapsed();
+            result.with_context(|| format!("Day {} part {} failed", solver.day, solver.part))?;
+            fastest = fastest.min(elapsed);
+            total += elapsed;
+            stats = measured;
+        }
+        println!("Day {} part {}: fastest {:?}, mean {:?} over {} runs{}",
+                 solver.day, solver.part, fastest, total / iterations, iterations, memory_usage(stats));
+    }
+    Ok(())
+}
+
+fn batch(args: &[String]) -> Result<()> {
+    let day = required_option(args, "--day")?;
+    let dir: String = required_option(args, "--dir")?;
+    let timeout = parsed_option(args, "--timeout")?.map(Duration::from_secs);
+    let reports = batch::run_dir(day, Path::new(&dir), &load_params(args)?, timeout)?;
+    for report in &reports {
+        print!("{}", report);
+    }
+    let flagged = reports.iter().filter(|report| report.is_flagged()).count();
+    if flagged > 0 {
+        bail!("{} of {} inputs flagged", flagged, reports.len());
+    }
+    println!("All {} inputs passed", reports.len());
+    Ok(())
+}
+
+fn check_examples(args: &[String]) -> Result<()> {
+    let day: Option<u8> = parsed_option(args, "--day")?;
+    let dir = option(args, "--dir").unwrap_or(examples::DIR);
+    let params = load_params(args)?;
+    let (mut passed, mut failed) = (0, 0);
+    for example in examples::load_manifest(dir)?.iter().filter(|example| day.is_none_or(|day| example.day == day)) {
+        for check in example.check(dir, &params, None, &Cancel::never())? {
+            println!("{} {}", example.name, check);
+            if check.passed() {
+                passed += 1;
+            } else {
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        bail!("{} of {} example checks failed", failed, passed + failed);
+    }
+    println!("All {} example checks passed", passed);
+    Ok(())
+}
+
+fn show_calendar() -> Result<()> {
+    let answers = Answers::load(answers::DEFAULT_PATH)?;
+    let history = History::load(history::DEFAULT_PATH)?;
+    print!("{}", calendar::render(&answers, &history));
+    Ok(())
+}
+
+fn explain(args: &[String]) -> Result<()> {
+    let day = required_option(args, "--day")?;
+    let part = required_option(args, "--part")?;
+    let explainer = solvers::explainer(day, part).with_context(|| format!("No explanation for day {} part {}", day, part))?;
+    let explanation = (explainer.explain)(&read_input(day, args)?, &load_params(args)?)?;
+    match option(args, "--output") {
+        None => print!("{}", explanation),
+        Some(path) => {
+            fs::write(path, explanation.to_string()).with_context(|| format!("Couldn't write {}", path))?;
+            println!("Answer: {} ({} records written to {})", explanation.answer, explanation.records.len(), path);
+        }
+    }
+    Ok(())
+}
+
+fn leaderboard(args: &[String]) -> Result<()> {
+    let path: String = required_option(args, "--file")?;
+    print!("{}", Leaderboard::load(path)?);
+    Ok(())
+}
+
+fn list(args: &[String]) -> Result<()> {
+    let history = History::load(history::DEFAULT_PATH)?;
+    if let Some(day) = parsed_option(args, "--day")? {
+        let puzzle = catalogue::puzzle(day).with_context(|| format!("No puzzle for day {}", day))?;
+        print!("{}", puzzle);
+        for part in 1..=2 {
+            if let Some(run) = history.last(day, part) {
+                println!("  Last part {} answer: {} ({:?})", part, run.answer, run.elapsed);
+            }
+        }
+        return Ok(());
+    }
+    println!("Day  Released    Stars  Title                   Part 1                            Part 2");
+    for puzzle in catalogue::PUZZLES {
+        let runs: Vec<Option<&Run>> = (1..=2).map(|part| history.last(puzzle.day, part)).collect();
+        let stars = "*".repeat(runs.iter().flatten().count());
+        let answers: Vec<String> = runs.iter()
+            .map(|run| run.map(|run| format!("{} ({:?})", run.answer, run.elapsed)).unwrap_or_else(|| "-".to_string()))
+            .collect();
+        println!("{:>3}  {:<10}  {:<5}  {:<22}  {:<32}  {}", puzzle.day, puzzle.released, stars, puzzle.title, answers[0], answers[1]);
+    }
+    Ok(())
+}
+
+fn serve(args: &[String]) -> Result<()> {
+    let addr = option(args, "--addr").unwrap_or("127.0.0.1:2024");
+    let timeout = parsed_option(args, "--timeout")?.unwrap_or(60);
+    let server = Server::bind(addr, Duration::from_secs(timeout), load_params(args)?)?;
+    println!("Listening on http://{}", server.local_addr()?);
+    server.serve()
+}
+
+fn repl(args: &[String]) -> Result<()> {
+    let mut repl = Repl::new(load_params(args)?);
+    if let Some(day) = parsed_option(args, "--day")? {
+        print!("{}", repl.load(day, None)?);
+    }
+    repl.interact(io::stdin().lock(), io::stdout())
+}
+
+fn watch(args: &[String]) -> Result<()> {
+    let day = required_option(args, "--day")?;
+    let interval = parsed_option(args, "--interval")?.unwrap_or(500);
+    watch::watch(day, Duration::from_millis(interval))
+}
+
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("batch") => batch(&args[1..]),
+        Some("calendar") => show_calendar(),
+        Some("check-examples") => check_examples(&args[1..]),
+        Some("explain") => explain(&args[1..]),
+        Some("leaderboard") => leaderboard(&args[1..]),
+        Some("list") => list(&args[1..]),
+        Some("serve") => serve(&args[1..]),
+        Some("repl") => repl(&args[1..]),
+        Some("watch") => watch(&args[1..]),
+        _ => bail!(USAGE),
+    }
+}
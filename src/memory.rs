@@ -0,0 +1,128 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static TOTAL: AtomicUsize = AtomicUsize::new(0);
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+pub struct CountingAllocator;
+
+fn record_alloc(size: usize) {
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+    TOTAL.fetch_add(size, Ordering::Relaxed);
+    COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    CURRENT.fetch_sub(size, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MemoryStats {
+    pub peak_bytes: usize,
+    pub allocated_bytes: usize,
+    pub allocations: usize,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "peak {}, {} allocations totalling {}", format_bytes(self.peak_bytes), self.allocations, format_bytes(self.allocated_bytes))
+    }
+}
+
+pub fn enabled() -> bool {
+    cfg!(feature = "count-alloc")
+}
+
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, MemoryStats) {
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let total = TOTAL.load(Ordering::Relaxed);
+    let count = COUNT.load(Ordering::Relaxed);
+    let result = f();
+    let stats = MemoryStats {
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(baseline),
+        allocated_bytes: TOTAL.load(Ordering::Relaxed) - total,
+        allocations: COUNT.load(Ordering::Relaxed) - count,
+    };
+    (result, stats)
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_allocations_made_through_it() {
+        let layout = Layout::from_size_align(4096, 8).unwrap();
+        let (_, stats) = measure(|| unsafe {
+            let ptr = CountingAllocator.alloc(layout);
+            assert!(!ptr.is_null());
+            CountingAllocator.dealloc(ptr, layout);
+        });
+        assert!(stats.allocations >= 1);
+        assert!(stats.allocated_bytes >= 4096);
+        assert!(stats.peak_bytes >= 4096);
+    }
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        assert_eq!(MemoryStats { peak_bytes: 2048, allocated_bytes: 4096, allocations: 3 }.to_string(), "peak 2.0 KiB, 3 allocations totalling 4.0 KiB");
+    }
+}
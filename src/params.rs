@@ -0,0 +1,144 @@
+use crate::config;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Params {
+    pub day02_max_step: i64,
+    pub day11_part1_blinks: usize,
+    pub day11_part2_blinks: usize,
+    pub day13_prize_offset: i64,
+    pub day14_map_x: i64,
+    pub day14_map_y: i64,
+    pub day14_seconds: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            day02_max_step: 3,
+            day11_part1_blinks: 25,
+            day11_part2_blinks: 75,
+            day13_prize_offset: 10000000000000,
+            day14_map_x: 101,
+            day14_map_y: 103,
+            day14_seconds: 100,
+        }
+    }
+}
+
+fn parse<T: std::str::FromStr>(key: &str, value: &str) -> Result<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    value.trim().parse().with_context(|| format!("Couldn't parse {} value: {}", key, value))
+}
+
+fn map_size(key: &str, value: &str) -> Result<i64> {
+    let size = parse(key, value)?;
+    if size <= 0 {
+        bail!("Expected a positive {} value, got: {}", key, value);
+    }
+    Ok(size)
+}
+
+impl Params {
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "day02.max_step" => self.day02_max_step = parse(key, value)?,
+            "day11.part1_blinks" => self.day11_part1_blinks = parse(key, value)?,
+            "day11.part2_blinks" => self.day11_part2_blinks = parse(key, value)?,
+            "day13.prize_offset" => self.day13_prize_offset = parse(key, value)?,
+            "day14.map_x" => self.day14_map_x = map_size(key, value)?,
+            "day14.map_y" => self.day14_map_y = map_size(key, value)?,
+            "day14.seconds" => self.day14_seconds = parse(key, value)?,
+            other => bail!("Unknown parameter: {}", other),
+        }
+        Ok(())
+    }
+
+    pub fn apply_config(&mut self, text: &str) -> Result<()> {
+        for section in config::parse(text)? {
+            for (key, value) in section.entries {
+                let key = if section.name.is_empty() { key } else { format!("{}.{}", section.name, key) };
+                self.set(&key, &value)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Params> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).with_context(|| format!("Couldn't read {}", path.display()))?;
+        let mut params = Params::default();
+        params.apply_config(&text).with_context(|| format!("Couldn't load {}", path.display()))?;
+        Ok(params)
+    }
+
+    pub fn apply_override(&mut self, assignment: &str) -> Result<()> {
+        let (key, value) = assignment.split_once('=').with_context(|| format!("Expected key=value, got: {}", assignment))?;
+        self.set(key.trim(), value)
+    }
+
+    pub fn entries(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("day02.max_step", self.day02_max_step.to_string()),
+            ("day11.part1_blinks", self.day11_part1_blinks.to_string()),
+            ("day11.part2_blinks", self.day11_part2_blinks.to_string()),
+            ("day13.prize_offset", self.day13_prize_offset.to_string()),
+            ("day14.map_x", self.day14_map_x.to_string()),
+            ("day14.map_y", self.day14_map_y.to_string()),
+            ("day14.seconds", self.day14_seconds.to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_known_parameters() -> Result<()> {
+        let mut params = Params::default();
+        params.set("day11.part2_blinks", "40")?;
+        params.set("day14.map_x", " 11 ")?;
+        assert_eq!(params.day11_part2_blinks, 40);
+        assert_eq!(params.day14_map_x, 11);
+        assert!(params.entries().contains(&("day14.map_x", "11".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_empty_and_negative_map_sizes() {
+        let mut params = Params::default();
+        assert_eq!(params.set("day14.map_x", "0").unwrap_err().to_string(), "Expected a positive day14.map_x value, got: 0");
+        assert_eq!(params.set("day14.map_y", " -7").unwrap_err().to_string(), "Expected a positive day14.map_y value, got:  -7");
+        assert_eq!(params, Params::default());
+    }
+
+    #[test]
+    fn apply_config_and_overrides() -> Result<()> {
+        let mut params = Params::default();
+        params.apply_config("[day02]\nmax_step = 4\n\n[day14]\nmap_x = 11\nmap_y = 7\n")?;
+        params.apply_override("day14.seconds=5")?;
+        assert_eq!(params, Params { day02_max_step: 4, day14_map_x: 11, day14_map_y: 7, day14_seconds: 5, ..Params::default() });
+        assert!(params.apply_config("[day14]\nmap_z = 1\n").is_err());
+        assert!(params.apply_override("day14.seconds").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn default_config_matches_defaults() -> Result<()> {
+        assert_eq!(Params::load("advent2024.toml")?, Params::default());
+        Ok(())
+    }
+
+    #[test]
+    fn reject_bad_parameters() {
+        let mut params = Params::default();
+        assert!(params.set("day99.nothing", "1").is_err());
+        assert!(params.set("day13.prize_offset", "lots").is_err());
+        assert_eq!(params, Params::default());
+    }
+}
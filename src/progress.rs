@@ -0,0 +1,84 @@
+use std::io::{stderr, Write};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const BAR_WIDTH: usize = 40;
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+pub trait Progress: Sync {
+    fn report(&self, done: usize, total: Option<usize>);
+}
+
+pub struct NoProgress;
+
+impl Progress for NoProgress {
+    fn report(&self, _done: usize, _total: Option<usize>) {}
+}
+
+impl<F: Fn(usize, Option<usize>) + Sync> Progress for F {
+    fn report(&self, done: usize, total: Option<usize>) {
+        self(done, total)
+    }
+}
+
+pub struct ProgressBar {
+    label: String,
+    last_drawn: Mutex<Option<Instant>>,
+}
+
+impl ProgressBar {
+    pub fn new(label: &str) -> ProgressBar {
+        ProgressBar { label: label.to_string(), last_drawn: Mutex::new(None) }
+    }
+
+    pub fn finish(&self) {
+        if self.last_drawn.lock().unwrap().take().is_some() {
+            eprint!("\r\x1b[2K");
+            let _ = stderr().flush();
+        }
+    }
+}
+
+impl Progress for ProgressBar {
+    fn report(&self, done: usize, total: Option<usize>) {
+        let mut last_drawn = self.last_drawn.lock().unwrap();
+        if last_drawn.is_some_and(|last| last.elapsed() < REDRAW_INTERVAL) {
+            return;
+        }
+        *last_drawn = Some(Instant::now());
+        eprint!("\r\x1b[2K{} {}", self.label, render(done, total));
+        let _ = stderr().flush();
+    }
+}
+
+pub fn render(done: usize, total: Option<usize>) -> String {
+    match total {
+        None => format!("{} steps", done),
+        Some(total) => {
+            let filled = (done.min(total) * BAR_WIDTH).checked_div(total).unwrap_or(BAR_WIDTH);
+            let percent = (done.min(total) * 100).checked_div(total).unwrap_or(100);
+            format!("[{}{}] {}/{} ({}%)", "#".repeat(filled), " ".repeat(BAR_WIDTH - filled), done, total, percent)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_bars_and_counts() {
+        assert_eq!(render(10, Some(40)), format!("[{}{}] 10/40 (25%)", "#".repeat(10), " ".repeat(30)));
+        assert_eq!(render(0, Some(0)), format!("[{}] 0/0 (100%)", "#".repeat(40)));
+        assert_eq!(render(1234, None), "1234 steps");
+    }
+
+    #[test]
+    fn closures_receive_reports() {
+        let reports = Mutex::new(vec![]);
+        let progress = |done, total| reports.lock().unwrap().push((done, total));
+        progress.report(1, Some(2));
+        progress.report(2, None);
+        assert_eq!(*reports.lock().unwrap(), vec![(1, Some(2)), (2, None)]);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::cancel::Cancel;
+use crate::params::Params;
+use crate::progress::NoProgress;
+use crate::solvers;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::io::{BufRead, Write};
+use std::time::Instant;
+
+const HELP: &str = "Commands:
+  load <day> [path]     load a day's input, res/dayNN.txt by default
+  run [part]            solve one or both parts of the loaded input
+  show                  print the parsed puzzle structures
+  grid                  render the puzzle map
+  set <param> <value>   change a puzzle parameter
+  params                list puzzle parameters
+  help                  show this message
+  quit                  leave the REPL
+";
+
+#[derive(Default)]
+pub struct Repl {
+    params: Params,
+    day: Option<u8>,
+    data: String,
+}
+
+pub enum Outcome {
+    Continue(String),
+    Quit,
+}
+
+impl Repl {
+    pub fn new(params: Params) -> Repl {
+        Repl { params, ..Repl::default() }
+    }
+
+    pub fn load(&mut self, day: u8, path: Option<&str>) -> Result<String> {
+        let path = path.map(str::to_string).unwrap_or_else(|| format!("res/day{:02}.txt", day));
+        let data = fs::read_to_string(&path).with_context(|| format!("Couldn't read {}", path))?;
+        self.day = Some(day);
+        self.data = data.trim_end().to_string();
+        Ok(format!("Loaded day {} from {} ({} lines)\n", day, path, self.data.lines().count()))
+    }
+
+    fn loaded_day(&self) -> Result<u8> {
+        self.day.with_context(|| "No input loaded, try: load <day>")
+    }
+
+    fn run(&self, part: Option<&str>) -> Result<String> {
+        let day = self.loaded_day()?;
+        let parts = match part {
+            None => vec![1, 2],
+            Some(part) => vec![part.parse().with_context(|| format!("Couldn't parse part: {}", part))?],
+        };
+        let mut out = String::new();
+        for part in parts {
+            let solver = solvers::find(day, part).with_context(|| format!("No solver for day {} part {}", day, part))?;
+            let started = Instant::now();
+            let answer = (solver.solve)(&self.data, &self.params, &Cancel::never(), &NoProgress)?;
+            out.push_str(&format!("Part {}: {} ({:?})\n", part, answer, started.elapsed()));
+        }
+        Ok(out)
+    }
+
+    fn show(&self) -> Result<String> {
+        let day = self.loaded_day()?;
+        match solvers::view(day).and_then(|view| view.describe) {
+            None => bail!("Day {} has nothing to show", day),
+            Some(describe) => describe(&self.data, &self.params),
+        }
+    }
+
+    fn grid(&self) -> Result<String> {
+        let day = self.loaded_day()?;
+        match solvers::view(day).and_then(|view| view.render) {
+            None => bail!("Day {} has no grid to render", day),
+            Some(render) => render(&self.data, &self.params),
+        }
+    }
+
+    fn params(&self) -> String {
+        self.params.entries().into_iter().map(|(key, value)| format!("{} = {}\n", key, value)).collect()
+    }
+
+    pub fn execute(&mut self, line: &str) -> Result<Outcome> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let output = match words.as_slice() {
+            [] => String::new(),
+            ["load", day] => self.load(day.parse().with_context(|| format!("Couldn't parse day: {}", day))?, None)?,
+            ["load", day, path] => self.load(day.parse().with_context(|| format!("Couldn't parse day: {}", day))?, Some(path))?,
+            ["run"] => self.run(None)?,
+            ["run", part] => self.run(Some(part))?,
+            ["show"] => self.show()?,
+            ["grid"] => self.grid()?,
+            ["set", key, value] => {
+                self.params.set(key, value)?;
+                format!("{} = {}\n", key, value)
+            }
+            ["params"] => self.params(),
+            ["help"] => HELP.to_string(),
+            ["quit"] | ["exit"] => return Ok(Outcome::Quit),
+            _ => bail!("Unknown command: {}, try: help", line.trim()),
+        };
+        Ok(Outcome::Continue(output))
+    }
+
+    pub fn interact<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> Result<()> {
+        write!(output, "> ")?;
+        output.flush()?;
+        for line in input.lines() {
+            match self.execute(&line?) {
+                Ok(Outcome::Quit) => return Ok(()),
+                Ok(Outcome::Continue(text)) => write!(output, "{}", text)?,
+                Err(e) => writeln!(output, "Error: {:#}", e)?,
+            }
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(commands: &str) -> Result<String> {
+        let mut output = vec![];
+        Repl::new(Params::default()).interact(commands.as_bytes(), &mut output)?;
+        Ok(String::from_utf8(output)?)
+    }
+
+    #[test]
+    fn runs_parts_with_tweaked_parameters() -> Result<()> {
+        let mut repl = Repl::new(Params::default());
+        repl.day = Some(11);
+        repl.data = "125 17".to_string();
+        repl.execute("set day11.part1_blinks 6")?;
+        match repl.execute("run 1")? {
+            Outcome::Continue(text) => assert!(text.starts_with("Part 1: 22 ("), "{}", text),
+            Outcome::Quit => panic!("Unexpected quit"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn shows_and_renders_loaded_input() -> Result<()> {
+        let output = session("load 14\nset day14.seconds 0\nshow\ngrid\nquit\nrun\n")?;
+        assert!(output.starts_with("> Loaded day 14 from res/day14.txt (500 lines)\n> day14.seconds = 0\n> 500 robots:\n  p=("), "{}", output);
+        assert!(output.ends_with("\n> "));
+        Ok(())
+    }
+
+    #[test]
+    fn reports_errors_and_continues() -> Result<()> {
+        assert_eq!(session("run\nfrobnicate\nset day99.x 1\n")?,
+                   "> Error: No input loaded, try: load <day>\n> Error: Unknown command: frobnicate, try: help\n> Error: Unknown parameter: day99.x\n> ");
+        Ok(())
+    }
+}
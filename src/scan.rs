@@ -0,0 +1,339 @@
+use alloc::format;
+use alloc::vec::Vec;
+use anyhow::{anyhow, bail, Context, Result};
+use core::fmt::{Display, Formatter};
+use core::marker::PhantomData;
+
+pub trait Integer: Copy {
+    const SIGNED: bool;
+    fn zero() -> Self;
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty => $signed:expr),*) => {
+        $(impl Integer for $t {
+            const SIGNED: bool = $signed;
+
+            fn zero() -> $t {
+                0
+            }
+
+            fn push_digit(self, digit: u8, negative: bool) -> Option<$t> {
+                let shifted = self.checked_mul(10)?;
+                if negative {
+                    shifted.checked_sub(digit as $t)
+                } else {
+                    shifted.checked_add(digit as $t)
+                }
+            }
+        })*
+    };
+}
+
+integer!(i32 => true, i64 => true, i128 => true, u8 => false, u32 => false, u64 => false, u128 => false, usize => false);
+
+pub struct Integers<'a, T> {
+    text: &'a str,
+    separators: &'a str,
+    pos: usize,
+    marker: PhantomData<T>,
+}
+
+impl<T: Integer> Integers<'_, T> {
+    fn fail(&mut self, error: anyhow::Error) -> Option<Result<T>> {
+        self.pos = self.text.len();
+        Some(Err(error))
+    }
+}
+
+impl<T: Integer> Iterator for Integers<'_, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Result<T>> {
+        let bytes = self.text.as_bytes();
+        let mut negative = false;
+        while self.pos < bytes.len() && !bytes[self.pos].is_ascii_digit() {
+            let byte = bytes[self.pos];
+            if byte == b'-' && bytes.get(self.pos + 1).is_some_and(u8::is_ascii_digit) {
+                if !T::SIGNED {
+                    return self.fail(anyhow!("Unexpected sign on unsigned number in: {}", self.text));
+                }
+                negative = true;
+            } else if !self.separators.as_bytes().contains(&byte) {
+                return self.fail(anyhow!("Unexpected {:?} in: {}", byte as char, self.text));
+            }
+            self.pos += 1;
+        }
+        if self.pos == bytes.len() {
+            return None;
+        }
+        let start = self.pos;
+        let mut value = Some(T::zero());
+        while self.pos < bytes.len() && bytes[self.pos].is_ascii_digit() {
+            value = value.and_then(|value| value.push_digit(bytes[self.pos] - b'0', negative));
+            self.pos += 1;
+        }
+        match value {
+            Some(value) => Some(Ok(value)),
+            None => {
+                let sign = if negative { "-" } else { "" };
+                self.fail(anyhow!("Number out of range: {}{}", sign, &self.text[start..self.pos]))
+            }
+        }
+    }
+}
+
+/// Reads every integer in `text`, which may only separate them with bytes from `separators`.
+pub fn integers<'a, T: Integer>(text: &'a str, separators: &'a str) -> Integers<'a, T> {
+    Integers { text, separators, pos: 0, marker: PhantomData }
+}
+
+pub fn fixed<T: Integer, const N: usize>(text: &str, separators: &str) -> Result<[T; N]> {
+    let mut values = [T::zero(); N];
+    let mut numbers = integers(text, separators);
+    for value in values.iter_mut() {
+        match numbers.next() {
+            Some(number) => *value = number?,
+            None => bail!("Expected {} numbers in: {}", N, text),
+        }
+    }
+    if let Some(extra) = numbers.next() {
+        extra?;
+        bail!("Expected {} numbers in: {}", N, text);
+    }
+    Ok(values)
+}
+
+pub fn number<T: Integer>(text: &str) -> Result<T> {
+    let trimmed = text.trim();
+    let digits = if T::SIGNED { trimmed.strip_prefix('-').unwrap_or(trimmed) } else { trimmed };
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        bail!("Expected a number, got: {}", text);
+    }
+    let negative = digits.len() < trimmed.len();
+    digits.bytes()
+        .try_fold(T::zero(), |value, byte| value.push_digit(byte - b'0', negative))
+        .with_context(|| format!("Number out of range: {}", trimmed))
+}
+
+pub fn list<T: Integer>(text: &str, separator: char) -> Result<Vec<T>> {
+    text.split(separator).map(number).collect()
+}
+
+pub fn split_pair<'a>(text: &'a str, separator: &str) -> Result<(&'a str, &'a str)> {
+    text.split_once(separator).with_context(|| format!("Expected \"{}\" in: {}", separator, text))
+}
+
+pub fn labelled<'a>(line: &'a str, label: &str) -> Result<&'a str> {
+    match line.strip_prefix(label).and_then(|rest| rest.strip_prefix(':')) {
+        Some(rest) => Ok(rest.trim()),
+        None => bail!("Expected \"{}:\" at start of: {}", label, line),
+    }
+}
+
+pub fn record<T: Integer, const N: usize>(line: &str, label: &str, separators: &str) -> Result<[T; N]> {
+    fixed(labelled(line, label)?, separators)
+}
+
+pub struct KeyValues<'a> {
+    text: &'a str,
+    pairs: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> KeyValues<'a> {
+    pub fn get(&self, key: &str) -> Result<&'a str> {
+        match self.pairs.iter().find(|(name, _)| *name == key) {
+            Some((_, value)) => Ok(value),
+            None => bail!("Missing {}= in: {}", key, self.text),
+        }
+    }
+}
+
+pub fn key_values(text: &str) -> Result<KeyValues<'_>> {
+    let pairs = text.split_whitespace().map(|pair| split_pair(pair, "=")).collect::<Result<_>>()?;
+    Ok(KeyValues { text, pairs })
+}
+
+pub struct Blocks<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let text = self.rest.trim_start_matches(['\r', '\n']);
+        let mut end = 0;
+        for line in text.split_inclusive('\n') {
+            if line.trim_end_matches(['\r', '\n']).is_empty() {
+                break;
+            }
+            end += line.len();
+        }
+        self.rest = &text[end..];
+        let block = text[..end].trim_end_matches(['\r', '\n']);
+        (!block.is_empty()).then_some(block)
+    }
+}
+
+pub fn blocks(text: &str) -> Blocks<'_> {
+    Blocks { rest: text }
+}
+
+pub fn write_joined<T: Display>(f: &mut Formatter<'_>, items: impl IntoIterator<Item = T>, separator: &str) -> core::fmt::Result {
+    for (i, item) in items.into_iter().enumerate() {
+        if i > 0 {
+            f.write_str(separator)?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+/// Displays a slice with [`write_joined`], for labels built outside a `Display` impl.
+pub struct Joined<'a, T>(pub &'a [T], pub &'a str);
+
+impl<T: Display> Display for Joined<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write_joined(f, self.0, self.1)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Grid<'a> {
+    bytes: &'a [u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+impl<'a> Grid<'a> {
+    pub fn parse(text: &'a str) -> Result<Grid<'a>> {
+        let bytes = text.as_bytes();
+        let width = bytes.iter().position(|&byte| byte == b'\n' || byte == b'\r').unwrap_or(bytes.len());
+        let stride = match bytes.get(width) {
+            Some(b'\r') => width + 2,
+            _ => width + 1,
+        };
+        let height = bytes.len().div_ceil(stride);
+        for row in 0..height {
+            let end = row * stride + width;
+            if end > bytes.len() || bytes[row * stride..end].iter().any(|&byte| byte == b'\n' || byte == b'\r') {
+                bail!("Row {} of grid is shorter than {} columns", row + 1, width);
+            }
+            if end < bytes.len() && bytes[end] != bytes[width] {
+                bail!("Row {} of grid is longer than {} columns", row + 1, width);
+            }
+        }
+        Ok(Grid { bytes, width, height, stride })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        if x < self.width && y < self.height {
+            Some(self.bytes[y * self.stride + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn digit(&self, x: usize, y: usize) -> Option<u8> {
+        self.get(x, y).filter(u8::is_ascii_digit).map(|byte| byte - b'0')
+    }
+
+    pub fn row(&self, y: usize) -> &'a [u8] {
+        &self.bytes[y * self.stride..y * self.stride + self.width]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_signed_and_unsigned_integers() -> Result<()> {
+        assert_eq!(integers::<i64>("p=0,4 v=3,-3", "pv=, ").collect::<Result<Vec<_>>>()?, vec![0, 4, 3, -3]);
+        assert_eq!(integers::<u64>("0,4 3,3", ", ").collect::<Result<Vec<_>>>()?, vec![0, 4, 3, 3]);
+        assert_eq!(integers::<i64>("-9223372036854775808", "").collect::<Result<Vec<_>>>()?, vec![i64::MIN]);
+        assert_eq!(integers::<u8>("255 256", " ").nth(1).unwrap().unwrap_err().to_string(), "Number out of range: 256");
+        assert_eq!(integers::<i64>("  ", " ").count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unexpected_bytes_and_signs() {
+        assert_eq!(integers::<u64>("3x 4", " ").next().unwrap().unwrap().to_string(), "3");
+        assert_eq!(integers::<u64>("3x 4", " ").collect::<Result<Vec<_>>>().unwrap_err().to_string(), "Unexpected 'x' in: 3x 4");
+        assert_eq!(integers::<u64>("3x 4", " ").count(), 2);
+        assert_eq!(integers::<u64>("-3   4", " ").next().unwrap().unwrap_err().to_string(), "Unexpected sign on unsigned number in: -3   4");
+        assert_eq!(integers::<i64>("- 3", " ").next().unwrap().unwrap_err().to_string(), "Unexpected '-' in: - 3");
+    }
+
+    #[test]
+    fn reads_fixed_records() -> Result<()> {
+        assert_eq!(fixed::<u64, 2>("3   4", " ")?, [3, 4]);
+        assert_eq!(fixed::<u64, 2>("3", " ").unwrap_err().to_string(), "Expected 2 numbers in: 3");
+        assert_eq!(fixed::<u64, 2>("3 4 5", " ").unwrap_err().to_string(), "Expected 2 numbers in: 3 4 5");
+        assert_eq!(fixed::<u64, 2>("3 4x", " ").unwrap_err().to_string(), "Unexpected 'x' in: 3 4x");
+        assert_eq!(fixed::<u64, 2>("-3   4", " ").unwrap_err().to_string(), "Unexpected sign on unsigned number in: -3   4");
+        Ok(())
+    }
+
+    #[test]
+    fn parses_single_numbers_and_lists() -> Result<()> {
+        assert_eq!(number::<i64>(" -42 ")?, -42);
+        assert_eq!(number::<u64>("-42").unwrap_err().to_string(), "Expected a number, got: -42");
+        assert_eq!(number::<u8>("300").unwrap_err().to_string(), "Number out of range: 300");
+        assert_eq!(list::<u64>("75,47,61", ',')?, vec![75, 47, 61]);
+        assert_eq!(list::<u64>("75,,61", ',').unwrap_err().to_string(), "Expected a number, got: ");
+        Ok(())
+    }
+
+    #[test]
+    fn splits_labelled_records_and_pairs() -> Result<()> {
+        assert_eq!(split_pair("47|53", "|")?, ("47", "53"));
+        assert_eq!(split_pair("4753", "|").unwrap_err().to_string(), "Expected \"|\" in: 4753");
+        assert_eq!(record::<i64, 2>("Button A: X+94, Y+34", "Button A", "XY+, ")?, [94, 34]);
+        assert_eq!(labelled("Prize: X=1", "Button B").unwrap_err().to_string(), "Expected \"Button B:\" at start of: Prize: X=1");
+        let pairs = key_values("p=0,4 v=3,-3")?;
+        assert_eq!((pairs.get("p")?, pairs.get("v")?), ("0,4", "3,-3"));
+        assert_eq!(pairs.get("q").unwrap_err().to_string(), "Missing q= in: p=0,4 v=3,-3");
+        assert_eq!(key_values("p=0,4 v").err().map(|e| e.to_string()), Some("Expected \"=\" in: v".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn separates_blank_line_blocks() {
+        assert_eq!(blocks("a\nb\n\nc\n").collect::<Vec<_>>(), vec!["a\nb", "c"]);
+        assert_eq!(blocks("a\r\nb\r\n\r\n\r\nc").collect::<Vec<_>>(), vec!["a\r\nb", "c"]);
+        assert_eq!(blocks("\n\n").count(), 0);
+    }
+
+    #[test]
+    fn joins_slices() {
+        assert_eq!(Joined(&[75, 47, 61], ",").to_string(), "75,47,61");
+        assert_eq!(Joined::<u64>(&[], ",").to_string(), "");
+    }
+
+    #[test]
+    fn indexes_grids_in_place() -> Result<()> {
+        let grid = Grid::parse("0123\n4567\n89ab")?;
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+        assert_eq!(grid.digit(1, 2), Some(9));
+        assert_eq!(grid.digit(2, 2), None);
+        assert_eq!(grid.get(4, 0), None);
+        assert_eq!(grid.row(1), b"4567");
+        assert_eq!(Grid::parse("01\r\n23\r\n")?.get(1, 1), Some(b'3'));
+        assert_eq!(Grid::parse("012\n34\n567").unwrap_err().to_string(), "Row 2 of grid is shorter than 3 columns");
+        assert_eq!(Grid::parse("01\n234\n56").unwrap_err().to_string(), "Row 2 of grid is longer than 2 columns");
+        Ok(())
+    }
+}
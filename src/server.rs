@@ -0,0 +1,278 @@
+use crate::cancel::{Cancel, Cancelled};
+use crate::json;
+use crate::params::Params;
+use crate::progress::NoProgress;
+use crate::solvers;
+use anyhow::{bail, Context, Result};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const MAX_BODY: usize = 16 * 1024 * 1024;
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(10);
+// Solvers that ignore cancellation keep running after their request times out, so cap how many run at once
+const MAX_SOLVES: usize = 8;
+
+pub struct Server {
+    listener: TcpListener,
+    timeout: Duration,
+    params: Params,
+    solves: Arc<AtomicUsize>,
+}
+
+/// A claim on one of the [`MAX_SOLVES`] solver threads, released when the solver finishes.
+struct SolveSlot(Arc<AtomicUsize>);
+
+impl SolveSlot {
+    fn acquire(solves: &Arc<AtomicUsize>) -> Option<SolveSlot> {
+        solves.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| (running < MAX_SOLVES).then_some(running + 1)).ok()?;
+        Some(SolveSlot(solves.clone()))
+    }
+}
+
+impl Drop for SolveSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: String,
+}
+
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Response {
+        Response { status, body: format!("{{\"error\":{}}}", json::quote(message)) }
+    }
+}
+
+impl Server {
+    pub fn bind<A: ToSocketAddrs>(addr: A, timeout: Duration, params: Params) -> Result<Server> {
+        let listener = TcpListener::bind(addr).with_context(|| "Couldn't bind listener")?;
+        Ok(Server { listener, timeout, params, solves: Arc::default() })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    pub fn serve(self) -> Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    eprintln!("Couldn't accept connection: {}", e);
+                    continue;
+                }
+            };
+            let timeout = self.timeout;
+            let params = self.params.clone();
+            let solves = self.solves.clone();
+            thread::spawn(move || {
+                if let Err(e) = handle_connection(stream, timeout, params, &solves) {
+                    eprintln!("Connection failed: {:#}", e);
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, timeout: Duration, params: Params, solves: &Arc<AtomicUsize>) -> Result<()> {
+    stream.set_read_timeout(Some(SOCKET_TIMEOUT))?;
+    let response = match read_request(&mut stream) {
+        Ok(request) => route(request, timeout, params, solves),
+        Err(e) => Response::error(400, &format!("{:#}", e)),
+    };
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Internal Server Error",
+    };
+    write!(stream, "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+           response.status, reason, response.body.len(), response.body)?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn read_request(stream: &mut TcpStream) -> Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().with_context(|| "Missing method")?.to_string();
+    let path = parts.next().with_context(|| "Missing path")?.to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            bail!("Connection closed in headers");
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().with_context(|| format!("Couldn't parse content length: {}", value))?;
+            }
+        }
+    }
+    if content_length > MAX_BODY {
+        bail!("Body too large: {} bytes", content_length);
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8(body).with_context(|| "Body isn't UTF-8")?;
+    Ok(Request { method, path, body })
+}
+
+fn parse_path(path: &str) -> Option<(u8, u8)> {
+    let mut segments = path.trim_matches('/').split('/');
+    match (segments.next(), segments.next(), segments.next(), segments.next(), segments.next(), segments.next()) {
+        (Some("2024"), Some("day"), Some(day), Some("part"), Some(part), None) => Some((day.parse().ok()?, part.parse().ok()?)),
+        _ => None,
+    }
+}
+
+fn route(request: Request, timeout: Duration, params: Params, solves: &Arc<AtomicUsize>) -> Response {
+    let Some((day, part)) = parse_path(&request.path) else {
+        return Response::error(404, &format!("Unknown path: {}", request.path));
+    };
+    if request.method != "POST" {
+        return Response::error(405, &format!("Expected POST, got {}", request.method));
+    }
+    let Some(solver) = solvers::find(day, part) else {
+        return Response::error(404, &format!("No solver for day {} part {}", day, part));
+    };
+
+    let Some(slot) = SolveSlot::acquire(solves) else {
+        return Response::error(503, &format!("Already running {} solvers, try again later", MAX_SOLVES));
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let input = request.body;
+    let started = Instant::now();
+    let cancel = Cancel::with_timeout(timeout);
+    let solver_cancel = cancel.clone();
+    thread::spawn(move || {
+        let _slot = slot;
+        let _ = sender.send((solver.solve)(input.trim_end(), &params, &solver_cancel, &NoProgress));
+    });
+    let result = receiver.recv_timeout(timeout);
+    cancel.cancel();
+    match result {
+        Ok(Ok(answer)) => Response {
+            status: 200,
+            body: format!("{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed_ms\":{:.3}}}",
+                          day, part, json::quote(&answer), started.elapsed().as_secs_f64() * 1000.0),
+        },
+        Ok(Err(e)) if e.is::<Cancelled>() => Response::error(504, &format!("Timed out after {:?}", timeout)),
+        Ok(Err(e)) => Response::error(400, &format!("{:#}", e)),
+        Err(RecvTimeoutError::Timeout) => Response::error(504, &format!("Timed out after {:?}", timeout)),
+        Err(RecvTimeoutError::Disconnected) => Response::error(500, "Solver panicked"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    fn start(timeout: Duration) -> Result<SocketAddr> {
+        start_with(timeout, Params::default())
+    }
+
+    fn start_with(timeout: Duration, params: Params) -> Result<SocketAddr> {
+        let server = Server::bind("127.0.0.1:0", timeout, params)?;
+        let addr = server.local_addr()?;
+        thread::spawn(move || server.serve());
+        Ok(addr)
+    }
+
+    fn send(addr: SocketAddr, method: &str, path: &str, body: &str) -> Result<(u16, String)> {
+        let mut stream = TcpStream::connect(addr)?;
+        write!(stream, "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}", method, path, body.len(), body)?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        let status = response.split_whitespace().nth(1).with_context(|| "Missing status")?.parse()?;
+        let (_, body) = response.split_once("\r\n\r\n").with_context(|| "Missing body")?;
+        Ok((status, body.to_string()))
+    }
+
+    #[test]
+    fn solves_posted_input() -> Result<()> {
+        let addr = start(Duration::from_secs(10))?;
+        let (status, body) = send(addr, "POST", "/2024/day/1/part/1", EXAMPLE)?;
+        assert_eq!(status, 200);
+        assert!(body.starts_with("{\"day\":1,\"part\":1,\"answer\":\"11\",\"elapsed_ms\":"), "{}", body);
+        Ok(())
+    }
+
+    #[test]
+    fn solves_with_configured_params() -> Result<()> {
+        let addr = start_with(Duration::from_secs(10), Params { day11_part1_blinks: 6, ..Params::default() })?;
+        let (_, body) = send(addr, "POST", "/2024/day/11/part/1", "125 17")?;
+        assert!(body.contains("\"answer\":\"22\""), "{}", body);
+        Ok(())
+    }
+
+    #[test]
+    fn handles_concurrent_requests() -> Result<()> {
+        let addr = start(Duration::from_secs(10))?;
+        let clients: Vec<_> = (1..=2)
+            .map(|part| thread::spawn(move || send(addr, "POST", &format!("/2024/day/1/part/{}", part), EXAMPLE)))
+            .collect();
+        let answers: Vec<_> = clients.into_iter().map(|client| client.join().unwrap()).collect::<Result<_>>()?;
+        assert!(answers[0].1.contains("\"answer\":\"11\""));
+        assert!(answers[1].1.contains("\"answer\":\"31\""));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_bad_requests() -> Result<()> {
+        let addr = start(Duration::from_secs(10))?;
+        assert_eq!(send(addr, "GET", "/2024/day/1/part/1", "")?.0, 405);
+        assert_eq!(send(addr, "POST", "/2024/day/26/part/1", EXAMPLE)?.0, 404);
+        assert_eq!(send(addr, "POST", "/elsewhere", EXAMPLE)?.0, 404);
+        assert_eq!(send(addr, "POST", "/2024/day/1/part/1", "not numbers")?.0, 400);
+        Ok(())
+    }
+
+    #[test]
+    fn enforces_timeout() -> Result<()> {
+        let addr = start(Duration::from_millis(1))?;
+        let (status, body) = send(addr, "POST", "/2024/day/6/part/2", include_str!("../res/day06.txt"))?;
+        assert_eq!(status, 504, "{}", body);
+        Ok(())
+    }
+
+    #[test]
+    fn limits_concurrent_solves() {
+        let solves = Arc::default();
+        let slots: Vec<SolveSlot> = (0..MAX_SOLVES).map_while(|_| SolveSlot::acquire(&solves)).collect();
+        assert_eq!(slots.len(), MAX_SOLVES);
+        assert!(SolveSlot::acquire(&solves).is_none());
+        drop(slots);
+        assert!(SolveSlot::acquire(&solves).is_some());
+    }
+}
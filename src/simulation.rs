@@ -0,0 +1,146 @@
+use alloc::boxed::Box;
+
+type OnFrame<'a, T> = Box<dyn FnMut(usize, &T) + 'a>;
+
+/// A discrete simulation: fixed rules that advance some state one step at a time.
+pub trait Simulation {
+    type State: Clone;
+
+    fn step(&self, state: &mut Self::State);
+
+    fn is_done(&self, _state: &Self::State) -> bool {
+        false
+    }
+}
+
+/// A copy of a runner's state that can be restored later.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot<T> {
+    pub steps: usize,
+    pub state: T,
+}
+
+/// Drives a simulation, counting steps and calling the frame callback after each one.
+pub struct Runner<'a, S: Simulation> {
+    simulation: S,
+    state: S::State,
+    steps: usize,
+    on_frame: Option<OnFrame<'a, S::State>>,
+}
+
+impl<'a, S: Simulation> Runner<'a, S> {
+    pub fn new(simulation: S, state: S::State) -> Runner<'a, S> {
+        Runner { simulation, state, steps: 0, on_frame: None }
+    }
+
+    pub fn on_frame<F: FnMut(usize, &S::State) + 'a>(mut self, on_frame: F) -> Runner<'a, S> {
+        self.on_frame = Some(Box::new(on_frame));
+        self
+    }
+
+    pub fn simulation(&self) -> &S {
+        &self.simulation
+    }
+
+    pub fn state(&self) -> &S::State {
+        &self.state
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.simulation.is_done(&self.state)
+    }
+
+    pub fn into_state(self) -> S::State {
+        self.state
+    }
+
+    pub fn step(&mut self) -> bool {
+        if self.is_done() {
+            return false;
+        }
+        self.simulation.step(&mut self.state);
+        self.steps += 1;
+        if let Some(on_frame) = &mut self.on_frame {
+            on_frame(self.steps, &self.state);
+        }
+        true
+    }
+
+    pub fn run(&mut self, steps: usize) -> usize {
+        (0..steps).take_while(|_| self.step()).count()
+    }
+
+    pub fn run_until<P: FnMut(&S::State) -> bool>(&mut self, mut predicate: P) -> bool {
+        while !predicate(&self.state) {
+            if !self.step() {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn finish(&mut self) -> usize {
+        let start = self.steps;
+        while self.step() {}
+        self.steps - start
+    }
+
+    pub fn snapshot(&self) -> Snapshot<S::State> {
+        Snapshot { steps: self.steps, state: self.state.clone() }
+    }
+
+    pub fn restore(&mut self, snapshot: Snapshot<S::State>) {
+        self.steps = snapshot.steps;
+        self.state = snapshot.state;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    struct Countdown;
+
+    impl Simulation for Countdown {
+        type State = u32;
+
+        fn step(&self, state: &mut u32) {
+            *state -= 1;
+        }
+
+        fn is_done(&self, state: &u32) -> bool {
+            *state == 0
+        }
+    }
+
+    #[test]
+    fn runs_steps_and_predicates() {
+        let mut runner = Runner::new(Countdown, 10);
+        assert_eq!(runner.run(3), 3);
+        assert_eq!(*runner.state(), 7);
+        assert!(runner.run_until(|&state| state == 5));
+        assert_eq!(runner.steps(), 5);
+        assert!(!runner.run_until(|&state| state == 20));
+        assert!(runner.is_done());
+        assert_eq!(runner.run(3), 0);
+        assert_eq!(runner.steps(), 10);
+    }
+
+    #[test]
+    fn restores_snapshots_and_reports_frames() {
+        let mut frames = vec![];
+        let mut runner = Runner::new(Countdown, 4).on_frame(|steps, &state| frames.push((steps, state)));
+        runner.run(1);
+        let snapshot = runner.snapshot();
+        assert_eq!(runner.finish(), 3);
+        runner.restore(snapshot);
+        assert_eq!(runner.steps(), 1);
+        assert_eq!(runner.into_state(), 3);
+        assert_eq!(frames, vec![(1, 3), (2, 2), (3, 1), (4, 0)]);
+    }
+}
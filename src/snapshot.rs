@@ -0,0 +1,69 @@
+use anyhow::{bail, Context, Result};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+const UPDATE_VAR: &str = "UPDATE_SNAPSHOTS";
+
+fn path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("res/snapshots").join(format!("{}.txt", name))
+}
+
+fn first_difference(expected: &str, actual: &str) -> Option<(usize, String, String)> {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    for line in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => break,
+            (expected, actual) if expected != actual => {
+                let show = |line: Option<&str>| line.map(str::to_string).unwrap_or_else(|| "<end of output>".to_string());
+                return Some((line, show(expected), show(actual)));
+            }
+            _ => {}
+        }
+    }
+    (expected != actual).then(|| {
+        let (newline, none) = ("<trailing newline>".to_string(), "<none>".to_string());
+        let line = expected.lines().count() + 1;
+        if expected.len() > actual.len() {
+            (line, newline, none)
+        } else {
+            (line, none, newline)
+        }
+    })
+}
+
+pub fn check(name: &str, actual: &str) -> Result<()> {
+    let path = path(name);
+    if env::var_os(UPDATE_VAR).is_some() {
+        fs::create_dir_all(path.parent().unwrap())?;
+        return fs::write(&path, actual).with_context(|| format!("Couldn't write {}", path.display()));
+    }
+    let expected = fs::read_to_string(&path)
+        .with_context(|| format!("Missing snapshot {}, rerun with {}=1 to create it", path.display(), UPDATE_VAR))?;
+    if let Some((line, expected, actual)) = first_difference(&expected, actual) {
+        bail!("Snapshot {} differs on line {}\n  expected: {}\n    actual: {}\nRerun with {}=1 to accept the new output",
+              name, line, expected, actual, UPDATE_VAR);
+    }
+    Ok(())
+}
+
+pub fn assert_snapshot(name: &str, actual: &str) {
+    if let Err(e) = check(name, actual) {
+        panic!("{:#}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_first_difference() {
+        assert_eq!(first_difference("a\nb\n", "a\nb\n"), None);
+        assert_eq!(first_difference("a\nb\nc\n", "a\nx\nc\n"), Some((2, "b".to_string(), "x".to_string())));
+        assert_eq!(first_difference("a\nb\n", "a\n"), Some((2, "b".to_string(), "<end of output>".to_string())));
+        assert_eq!(first_difference("a\n", "a"), Some((2, "<trailing newline>".to_string(), "<none>".to_string())));
+        assert_eq!(first_difference("a", "a\n"), Some((2, "<none>".to_string(), "<trailing newline>".to_string())));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13, day14, day15};
+use crate::cache;
+use crate::cancel::Cancel;
+use crate::explain::Explanation;
+use crate::params::Params;
+use crate::progress::Progress;
+use anyhow::Result;
+
+pub struct Solver {
+    pub day: u8,
+    pub part: u8,
+    pub version: u64,
+    pub solve: fn(&str, &Params, &Cancel, &dyn Progress) -> Result<String>,
+}
+
+/// Hash of the helpers the solvers share; changing any of them may change any day's answers.
+const SHARED_VERSION: u64 = {
+    let sources = [include_str!("bigint.rs"), include_str!("bitgrid.rs"), include_str!("cycle.rs"), include_str!("scan.rs"), include_str!("simulation.rs")];
+    let mut hash = cache::FNV_OFFSET;
+    let mut i = 0;
+    while i < sources.len() {
+        hash = cache::fnv1a(hash, sources[i].as_bytes());
+        i += 1;
+    }
+    hash
+};
+
+/// A solver's version is a hash of its day's source and the shared helpers, so cached answers are
+/// invalidated whenever the code that produced them changes.
+const fn source_version(source: &str) -> u64 {
+    cache::fnv1a(SHARED_VERSION, source.as_bytes())
+}
+
+pub const SOLVERS: &[Solver] = &[
+    Solver { day: 1, part: 1, version: source_version(include_str!("day01.rs")), solve: |data, _, _, _| Ok(day01::sum_differences(data)?.to_string()) },
+    Solver { day: 1, part: 2, version: source_version(include_str!("day01.rs")), solve: |data, _, _, _| Ok(day01::similarity_score(data)?.to_string()) },
+    Solver { day: 2, part: 1, version: source_version(include_str!("day02.rs")), solve: |data, params, _, _| Ok(day02::count_pure_safe_reports(data, params.day02_max_step)?.to_string()) },
+    Solver { day: 2, part: 2, version: source_version(include_str!("day02.rs")), solve: |data, params, _, _| Ok(day02::count_dampened_safe_reports(data, params.day02_max_step)?.to_string()) },
+    Solver { day: 3, part: 1, version: source_version(include_str!("day03.rs")), solve: |data, _, _, _| Ok(day03::add_all_muls(data)?.to_string()) },
+    Solver { day: 3, part: 2, version: source_version(include_str!("day03.rs")), solve: |data, _, _, _| Ok(day03::add_all_muls(&day03::clean_do_donts(data)?)?.to_string()) },
+    Solver { day: 4, part: 1, version: source_version(include_str!("day04.rs")), solve: |data, _, _, _| Ok(day04::find_xmas(data)?.to_string()) },
+    Solver { day: 4, part: 2, version: source_version(include_str!("day04.rs")), solve: |data, _, _, _| Ok(day04::find_x_mas(data)?.to_string()) },
+    Solver { day: 5, part: 1, version: source_version(include_str!("day05.rs")), solve: |data, _, _, _| Ok(day05::sum_valid_middle_pages(data)?.to_string()) },
+    Solver { day: 5, part: 2, version: source_version(include_str!("day05.rs")), solve: |data, _, _, _| Ok(day05::sum_corrected_invalid_middle_pages(data)?.to_string()) },
+    Solver { day: 6, part: 1, version: source_version(include_str!("day06.rs")), solve: |data, _, _, _| Ok(day06::count_path(data)?.to_string()) },
+    Solver { day: 6, part: 2, version: source_version(include_str!("day06.rs")), solve: |data, _, cancel, progress| Ok(day06::count_guard_loops(data, cancel, progress)?.to_string()) },
+    Solver { day: 7, part: 1, version: source_version(include_str!("day07.rs")), solve: |data, _, _, _| Ok(day07::total_calibration_result(data)?.to_string()) },
+    Solver { day: 7, part: 2, version: source_version(include_str!("day07.rs")), solve: |data, _, _, _| Ok(day07::total_calibration_with_concat_result(data)?.to_string()) },
+    Solver { day: 8, part: 1, version: source_version(include_str!("day08.rs")), solve: |data, _, _, _| Ok(day08::unique_antinode_locations(data)?.to_string()) },
+    Solver { day: 8, part: 2, version: source_version(include_str!("day08.rs")), solve: |data, _, _, _| Ok(day08::unique_antinode_locations_with_harmonics(data)?.to_string()) },
+    Solver { day: 9, part: 1, version: source_version(include_str!("day09.rs")), solve: |data, _, _, _| Ok(day09::checksum_compacted_disk_naive(data)?.to_string()) },
+    Solver { day: 9, part: 2, version: source_version(include_str!("day09.rs")), solve: |data, _, _, _| Ok(day09::checksum_compacted_disk_smart(data)?.to_string()) },
+    Solver { day: 10, part: 1, version: source_version(include_str!("day10.rs")), solve: |data, _, _, _| Ok(day10::sum_of_trailhead_unique_ends(data)?.to_string()) },
+    Solver { day: 10, part: 2, version: source_version(include_str!("day10.rs")), solve: |data, _, _, _| Ok(day10::sum_of_trailhead_paths(data)?.to_string()) },
+    Solver { day: 11, part: 1, version: source_version(include_str!("day11.rs")), solve: |data, params, _, _| Ok(day11::count_stones(data, params.day11_part1_blinks)?.to_string()) },
+    Solver { day: 11, part: 2, version: source_version(include_str!("day11.rs")), solve: |data, params, _, _| Ok(day11::count_stones(data, params.day11_part2_blinks)?.to_string()) },
+    Solver { day: 12, part: 1, version: source_version(include_str!("day12.rs")), solve: |data, _, _, _| Ok(day12::total_fencing_price(data)?.to_string()) },
+    Solver { day: 12, part: 2, version: source_version(include_str!("day12.rs")), solve: |data, _, _, _| Ok(day12::discounted_fencing_price(data)?.to_string()) },
+    Solver { day: 13, part: 1, version: source_version(include_str!("day13.rs")), solve: |data, _, _, _| Ok(day13::min_spend_for_most_prizes(data, 0)?.to_string()) },
+    Solver { day: 13, part: 2, version: source_version(include_str!("day13.rs")), solve: |data, params, _, _| Ok(day13::min_spend_for_most_prizes(data, params.day13_prize_offset)?.to_string()) },
+    Solver { day: 14, part: 1, version: source_version(include_str!("day14.rs")), solve: |data, params, _, _| Ok(day14::safety_factor(data, params.day14_map_x, params.day14_map_y, params.day14_seconds)?.to_string()) },
+    Solver { day: 14, part: 2, version: source_version(include_str!("day14.rs")), solve: |data, params, cancel, progress| Ok(day14::find_picture(data, params.day14_map_x, params.day14_map_y, cancel, progress)?.to_string()) },
+    Solver { day: 15, part: 1, version: source_version(include_str!("day15.rs")), solve: |data, _, _, _| Ok(day15::sum_of_gps_coordinates(data, false)?.to_string()) },
+    Solver { day: 15, part: 2, version: source_version(include_str!("day15.rs")), solve: |data, _, _, _| Ok(day15::sum_of_gps_coordinates(data, true)?.to_string()) },
+];
+
+pub struct View {
+    pub day: u8,
+    pub describe: Option<fn(&str, &Params) -> Result<String>>,
+    pub render: Option<fn(&str, &Params) -> Result<String>>,
+}
+
+pub const VIEWS: &[View] = &[
+    View { day: 5, describe: Some(|data, _| day05::describe(data)), render: None },
+    View { day: 6, describe: None, render: Some(|data, _| day06::render_path(data)) },
+    View { day: 7, describe: Some(|data, _| day07::describe(data)), render: None },
+    View { day: 11, describe: Some(|data, _| day11::describe(data)), render: None },
+    View { day: 12, describe: Some(|data, _| day12::describe(data)), render: None },
+    View { day: 13, describe: Some(|data, params| day13::describe(data, params.day13_prize_offset)), render: None },
+    View {
+        day: 14,
+        describe: Some(|data, _| day14::describe(data)),
+        render: Some(|data, params| day14::render_robots(data, params.day14_map_x, params.day14_map_y, params.day14_seconds)),
+    },
+    View { day: 15, describe: None, render: Some(|data, _| day15::render_warehouse(data, true)) },
+];
+
+pub struct Explainer {
+    pub day: u8,
+    pub part: u8,
+    pub explain: fn(&str, &Params) -> Result<Explanation>,
+}
+
+pub const EXPLAINERS: &[Explainer] = &[
+    Explainer { day: 2, part: 1, explain: |data, params| day02::explain_safe_reports(data, params.day02_max_step, false) },
+    Explainer { day: 2, part: 2, explain: |data, params| day02::explain_safe_reports(data, params.day02_max_step, true) },
+    Explainer { day: 5, part: 1, explain: |data, _| day05::explain_updates(data, false) },
+    Explainer { day: 5, part: 2, explain: |data, _| day05::explain_updates(data, true) },
+    Explainer { day: 7, part: 1, explain: |data, _| day07::explain_calibrations(data, false) },
+    Explainer { day: 7, part: 2, explain: |data, _| day07::explain_calibrations(data, true) },
+    Explainer { day: 13, part: 1, explain: |data, _| day13::explain_prizes(data, 0) },
+    Explainer { day: 13, part: 2, explain: |data, params| day13::explain_prizes(data, params.day13_prize_offset) },
+];
+
+pub fn find(day: u8, part: u8) -> Option<&'static Solver> {
+    SOLVERS.iter().find(|solver| solver.day == day && solver.part == part)
+}
+
+pub fn explainer(day: u8, part: u8) -> Option<&'static Explainer> {
+    EXPLAINERS.iter().find(|explainer| explainer.day == day && explainer.part == part)
+}
+
+pub fn view(day: u8) -> Option<&'static View> {
+    VIEWS.iter().find(|view| view.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::progress::NoProgress;
+
+    #[test]
+    fn every_day_has_both_parts() {
+        for day in 1..=15 {
+            assert!(find(day, 1).is_some(), "day {} part 1 missing", day);
+            assert!(find(day, 2).is_some(), "day {} part 2 missing", day);
+        }
+        assert!(find(16, 1).is_none());
+    }
+
+    #[test]
+    fn versions_follow_each_days_source() {
+        assert_eq!(find(6, 1).unwrap().version, find(6, 2).unwrap().version);
+        assert_ne!(find(6, 1).unwrap().version, find(7, 1).unwrap().version);
+        assert_eq!(find(6, 1).unwrap().version, source_version(include_str!("day06.rs")));
+        assert_ne!(source_version("fn solve() {}"), source_version("fn solve() { }"));
+    }
+
+    #[test]
+    fn solves_through_registry() -> Result<()> {
+        let params = Params::default();
+        assert_eq!((find(1, 1).unwrap().solve)("3   4\n4   3\n2   5\n1   3\n3   9\n3   3", &params, &Cancel::never(), &NoProgress)?, "11");
+        assert_eq!((find(11, 1).unwrap().solve)("125 17", &params, &Cancel::never(), &NoProgress)?, "55312");
+        assert_eq!((find(11, 2).unwrap().solve)("125 17", &Params { day11_part2_blinks: 6, ..params }, &Cancel::never(), &NoProgress)?, "22");
+        Ok(())
+    }
+
+    #[test]
+    fn explanations_agree_with_solvers() -> Result<()> {
+        let params = Params::default();
+        for explainer in EXPLAINERS {
+            let data = std::fs::read_to_string(format!("res/day{:02}.txt", explainer.day))?;
+            let answer = (find(explainer.day, explainer.part).unwrap().solve)(&data, &params, &Cancel::never(), &NoProgress)?;
+            assert_eq!((explainer.explain)(&data, &params)?.answer, answer, "day {} part {}", explainer.day, explainer.part);
+        }
+        Ok(())
+    }
+}
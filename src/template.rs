@@ -1,5 +1,4 @@
 #[allow(dead_code, unused_imports)]
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,159 @@
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Report {
+    pub answers: BTreeMap<u8, String>,
+    pub examples: Vec<(String, bool)>,
+}
+
+fn watched_paths(day: u8) -> Vec<PathBuf> {
+    let mut paths = vec![
+        PathBuf::from(format!("res/day{:02}.txt", day)),
+        PathBuf::from(format!("src/day{:02}.rs", day)),
+    ];
+    if let Ok(entries) = fs::read_dir("res/examples") {
+        let prefix = format!("day{:02}", day);
+        let mut examples: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with(&prefix)))
+            .collect();
+        examples.sort();
+        paths.extend(examples);
+    }
+    paths
+}
+
+fn modified_times(paths: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>)> {
+    paths.iter().map(|path| (path.clone(), fs::metadata(path).and_then(|metadata| metadata.modified()).ok())).collect()
+}
+
+pub fn parse_report(day: u8, output: &str) -> Report {
+    let module = format!("day{:02}::tests::", day);
+    let mut report = Report::default();
+    let mut lines = output.lines();
+    while let Some(line) = lines.next() {
+        if let Some(rest) = line.strip_prefix("test ").and_then(|rest| rest.strip_prefix(&module)) {
+            if let Some((name, result)) = rest.split_once(" ... ") {
+                if name.starts_with("example") {
+                    report.examples.push((name.to_string(), result == "ok"));
+                }
+            }
+        } else if line.starts_with("---- ") && line.contains(&module) {
+            for line in lines.by_ref().take_while(|line| !line.is_empty()) {
+                if let Some((part, answer)) = line.strip_prefix("Part ").and_then(|rest| rest.split_once(": ")) {
+                    if let Ok(part) = part.parse() {
+                        report.answers.insert(part, answer.to_string());
+                    }
+                }
+            }
+        }
+    }
+    report
+}
+
+pub fn run_day(day: u8) -> Result<Report> {
+    let output = Command::new("cargo")
+        .args(["test", "--lib", &format!("day{:02}::tests::", day), "--", "--show-output", "--test-threads=1"])
+        .output()
+        .with_context(|| "Couldn't run cargo test")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let report = parse_report(day, &stdout);
+    if report.answers.is_empty() && report.examples.is_empty() {
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+    Ok(report)
+}
+
+pub fn diff(previous: Option<&Report>, current: &Report) -> String {
+    let mut out = String::new();
+    for (name, passed) in current.examples.iter() {
+        out.push_str(&format!("{}: {}\n", name, if *passed { "ok" } else { "FAILED" }));
+    }
+    for (part, answer) in current.answers.iter() {
+        match previous.and_then(|previous| previous.answers.get(part)) {
+            None => out.push_str(&format!("Part {}: {}\n", part, answer)),
+            Some(old) if old == answer => out.push_str(&format!("Part {}: {} (unchanged)\n", part, answer)),
+            Some(old) => out.push_str(&format!("Part {}: {} -> {} (changed)\n", part, old, answer)),
+        }
+    }
+    if let Some(previous) = previous {
+        for part in previous.answers.keys().filter(|part| !current.answers.contains_key(part)) {
+            out.push_str(&format!("Part {}: {} -> no answer\n", part, previous.answers[part]));
+        }
+    }
+    out
+}
+
+pub fn watch(day: u8, interval: Duration) -> Result<()> {
+    let mut seen = vec![];
+    let mut previous = None;
+    loop {
+        let paths = watched_paths(day);
+        let times = modified_times(&paths);
+        if times != seen {
+            let changed: Vec<String> = times.iter().filter(|time| !seen.contains(*time)).map(|(path, _)| path.display().to_string()).collect();
+            println!("Running day {} after changes to {}", day, changed.join(", "));
+            let report = run_day(day)?;
+            print!("{}", diff(previous.as_ref(), &report));
+            previous = Some(report);
+            seen = times;
+        }
+        thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = r"
+running 4 tests
+test day06::tests::example_1 ... ok
+test day06::tests::example_2 ... FAILED
+test day06::tests::part_1 ... ok
+test day06::tests::part_2 ... ok
+
+successes:
+
+---- day06::tests::part_1 stdout ----
+Part 1: 5312
+
+---- day06::tests::part_2 stdout ----
+Part 2: 1748
+
+
+successes:
+    day06::tests::example_1
+";
+
+    #[test]
+    fn parses_test_output() {
+        let report = parse_report(6, OUTPUT);
+        assert_eq!(report.examples, vec![("example_1".to_string(), true), ("example_2".to_string(), false)]);
+        assert_eq!(report.answers, BTreeMap::from([(1, "5312".to_string()), (2, "1748".to_string())]));
+        assert_eq!(parse_report(7, OUTPUT), Report::default());
+    }
+
+    #[test]
+    fn diffs_against_previous_run() {
+        let previous = Report { answers: BTreeMap::from([(1, "41".to_string()), (2, "6".to_string())]), examples: vec![] };
+        let current = Report { answers: BTreeMap::from([(1, "41".to_string())]), examples: vec![("example_1".to_string(), true)] };
+        assert_eq!(diff(None, &previous), "Part 1: 41\nPart 2: 6\n");
+        assert_eq!(diff(Some(&previous), &current), "example_1: ok\nPart 1: 41 (unchanged)\nPart 2: 6 -> no answer\n");
+        assert_eq!(diff(Some(&current), &previous), "Part 1: 41 (unchanged)\nPart 2: 6\n");
+    }
+
+    #[test]
+    fn watches_input_and_source() {
+        let paths = watched_paths(6);
+        assert_eq!(paths[..2], [PathBuf::from("res/day06.txt"), PathBuf::from("src/day06.rs")]);
+        assert!(modified_times(&paths).iter().take(2).all(|(_, time)| time.is_some()));
+    }
+}